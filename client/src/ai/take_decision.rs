@@ -1,262 +1,197 @@
-pub fn start_test(message: &str) -> String {
-    let mut result = String::new();
-    let mut started = false;
+use protocol::{Action, ClientMessage, Command, PlayerAction, Snapshot};
 
-    for line in message.lines() {
-        if line.starts_with("start") ||
-        line.starts_with("play") ||
-        line.starts_with("free-kick") ||
-        line.starts_with("penalty-kick") ||
-        line.starts_with("transformation-kick") {
-            if line.starts_with("start") {
-                print!("GAME START DETECTED\n");
-                started = true;
-            }
-            result.push_str(&format!("play\n"));
-            continue;
-        }
-
-        if line.trim().starts_with("scrum") {
-            return "error".to_string();
-        }
-
-        if line.trim().is_empty() {
-            continue;
-        }
+pub fn start_test(snapshot: &Snapshot) -> ClientMessage {
+    let mut actions = Vec::new();
+    let started = snapshot.name == "start";
+    let loose = !snapshot.players.iter().any(|p| p.has_ball);
 
-        if line.starts_with("time:") {
-            continue;
-        }
+    if started {
+        println!("GAME START DETECTED");
+    }
 
-        if let Some((key, info)) = line.split_once(':') {
-            if info.contains("/B:") && started {
-                result.push_str(&format!("{}:K315/30\n", key));
-                continue;
+    for player in &snapshot.players {
+        let action = if player.has_ball && started {
+            Action::Kick { direction: 315.0, high: 30.0 }
+        } else if player.has_ball {
+            if player.x > 112.0 {
+                Action::Try
+            } else if player.x > 80.0 {
+                Action::Run { direction: 0.0 }
+            } else {
+                Action::Pass { direction: 135.0 }
             }
-            if info.contains("/B:") {
-                if let Some((dist, more_info)) = info.trim().split_once(' ') {
-                    if dist.parse::<f32>().unwrap_or(0.0) > 112.0 && more_info != "" {
-                            result.push_str(&format!("{}:G\n", key));
-                            continue;
-                    } else if dist.parse::<f32>().unwrap_or(0.0) > 80.0 {
-                        result.push_str(&format!("{}:R0\n", key));
-                        continue;
-                    } else {
-                        result.push_str(&format!("{}:P135\n", key));
-                        continue;
-                    }
-                }
+        } else if loose && player.team != snapshot.team {
+            // Defenders go under a kick and call the mark as they catch it
+            let dx = snapshot.ball.x - player.x;
+            let dy = snapshot.ball.y - player.y;
+            let distance = (dx * dx + dy * dy).sqrt();
+            if distance < 3.0 {
+                Action::Mark
+            } else if distance < 6.0 {
+                Action::Run { direction: dy.atan2(dx).to_degrees().rem_euclid(360.0) }
+            } else {
+                Action::Stay
             }
-            result.push_str(&format!("{}:{}\n", key, "S"));
-            continue;
-        }
-        // if line.trim() != "" {
-        //     print!("don't know line: \"{}\"\n", line);
-        // }
+        } else {
+            Action::Stay
+        };
+        actions.push(PlayerAction { team: player.team, number: player.number, action });
     }
 
-    if !result.starts_with("play") {
-        result.insert_str(0, "play\n");
-    }
-    return result;
+    ClientMessage::Actions { state: "play".to_string(), actions }
 }
 
-pub fn scrum_test(message: &str) -> String {
-    let mut result = String::new();
-
-    for line in message.lines() {
-        if line.starts_with("scrum") {
-            let val = line.trim();
-            result.push_str(&format!("{}\n", val));
-            continue;
-        }
-
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        if line.starts_with("time:") {
-            continue;
-        }
-
-        if let Some((key, _)) = line.split_once(':') {
-            result.push_str(&format!("{}:{}\n", key, "S"));
-        }
+pub fn drop_out_test(snapshot: &Snapshot) -> ClientMessage {
+    let mut actions = Vec::new();
+    // Drop the ball toward the opponents
+    let opponents: Vec<f32> = snapshot.players.iter().filter(|p| p.team != snapshot.team).map(|p| p.x).collect();
+    let target = opponents.iter().sum::<f32>() / opponents.len().max(1) as f32;
+
+    for player in &snapshot.players {
+        let action = if player.has_ball {
+            Action::Kick { direction: if target > player.x { 0.0 } else { 180.0 }, high: 40.0 }
+        } else {
+            Action::Stay
+        };
+        actions.push(PlayerAction { team: player.team, number: player.number, action });
     }
 
-    return result;
+    ClientMessage::Actions { state: snapshot.name.clone(), actions }
 }
 
-pub fn offside_test(message: &str) -> String {
-    let mut result = String::new();
-
-    for line in message.lines() {
-        if line.starts_with("offside") {
-            let val = line.trim();
-            result.push_str(&format!("{}\n", val));
-            continue;
-        }
-
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        if line.starts_with("time:") {
-            continue;
-        }
-
-        if let Some((key, _)) = line.split_once(':') {
-            result.push_str(&format!("{}:{}\n", key, "S"));
-        }
+pub fn scrum_test(snapshot: &Snapshot) -> ClientMessage {
+    let mut actions = Vec::new();
+    for player in &snapshot.players {
+        // Both hookers strike, the number 8 whose pack wins the ball picks up
+        let action = match player.number {
+            2 | 8 => Action::Take,
+            _ => Action::Stay,
+        };
+        actions.push(PlayerAction { team: player.team, number: player.number, action });
     }
-
-    return result;
+    ClientMessage::Actions { state: snapshot.name.clone(), actions }
 }
 
-pub fn ruck_test(message: &str) -> String {
-    let mut result = String::new();
-    let mut time = 0;
-
-    for line in message.lines() {
-
-        if line.starts_with("ruck") {
-            let val: &str = line.trim();
-            result.push_str(&format!("{}\n", val));
-            continue;
-        }
-
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        if line.starts_with("time:") {
-            if let Some((_, value)) = line.split_once(':') {
-                if let Ok(parsed_time) = value.trim().parse::<u64>() {
-                    time = parsed_time;
-                }
-            }
-            continue;
-        }
-
-        if let Some((key, _)) = line.split_once(':') {
-            if time == 0 {
-                if key == "A3" {
-                    result.push_str(&format!("{}:{}\n", key, "R15"));
-                    continue;
-                }
-                if key == "A5" {
-                    result.push_str(&format!("{}:{}\n", key, "R180"));
-                    continue;
-                }
-                if key == "H4" {
-                    result.push_str(&format!("{}:{}\n", key, "R0"));
-                    continue;
-                }
+pub fn maul_test(snapshot: &Snapshot) -> ClientMessage {
+    stay(snapshot)
+}
 
-                if key == "H5" {
-                    result.push_str(&format!("{}:{}\n", key, "R0"));
-                    continue;
-                }
-                if key == "H9" {
-                    result.push_str(&format!("{}:{}\n", key, "R0"));
-                    continue;
-                }
-            }
-            if time == 25 && key == "H9" {
-                result.push_str(&format!("{}:{}\n", key, "T"));
-                continue;
-            }
-            if time == 50 && key == "H9" {
-                result.push_str(&format!("{}:{}\n", key, "P135"));
-                continue;
-            }
-            result.push_str(&format!("{}:{}\n", key, "S"));
-        }
+pub fn line_out_test(snapshot: &Snapshot) -> ClientMessage {
+    let mut actions = Vec::new();
+    // Throw straight in from the touch line the ball went out
+    let straight = if snapshot.ball.y <= 1.0 { 90.0 } else { 270.0 };
+
+    for player in &snapshot.players {
+        let action = match player.number {
+            _ if player.has_ball => Action::Throw { angle: straight },
+            4 => Action::Jump,
+            1 | 6 => Action::Lift,
+            _ => Action::Stay,
+        };
+        actions.push(PlayerAction { team: player.team, number: player.number, action });
     }
 
-    return result;
+    ClientMessage::Actions { state: snapshot.name.clone(), actions }
 }
 
-pub fn penalty_test(message: &str) -> String {
-    let mut result = String::new();
-
-    for line in message.lines() {
-        if line.starts_with("start") || line.starts_with("play") {
-            if line.starts_with("start") {
-                print!("GAME START DETECTED\n");
-            }
-            result.push_str(&format!("play\n"));
-            continue;
-        }
-
-        if line.starts_with("set-penalty") {
-            let val: &str = line.trim();
-            result.push_str(&format!("{}\n", val));
-            continue;
-        }
-
-        if line.trim().is_empty() {
-            continue;
-        }
+pub fn offside_test(snapshot: &Snapshot) -> ClientMessage {
+    stay(snapshot)
+}
 
-        if line.starts_with("time:") {
-            continue;
-        }
+pub fn ruck_test(snapshot: &Snapshot) -> ClientMessage {
+    let mut actions = Vec::new();
+    let time = snapshot.time;
+
+    for player in &snapshot.players {
+        let key = format!("{}{}", player.team, player.number);
+        let action = match (time, key.as_str()) {
+            (0, "A3") => Action::Run { direction: 15.0 },
+            (0, "A5") => Action::Run { direction: 180.0 },
+            (0, "H4") | (0, "H5") | (0, "H9") => Action::Run { direction: 0.0 },
+            (25, "H9") => Action::Take,
+            (50, "H9") => Action::Pass { direction: 135.0 },
+            _ => Action::Stay,
+        };
+        actions.push(PlayerAction { team: player.team, number: player.number, action });
     }
 
-    result.push_str(&format!("K/10/315/30\n"));
-    //result.push_str(&format!("P/10/18/35\n"));
-    // result.push_str(&format!("S\n"));
-
-    return result;
+    ClientMessage::Actions { state: snapshot.name.clone(), actions }
 }
 
-pub fn transformation_test(message: &str) -> String {
-    let mut result = String::new();
-
-    for line in message.lines() {
-
-        if line.starts_with("set-transformation") {
-            let val: &str = line.trim();
-            result.push_str(&format!("{}\n", val));
-            continue;
-        }
-
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        if line.starts_with("time:") {
-            continue;
-        }
+pub fn penalty_test(snapshot: &Snapshot) -> ClientMessage {
+    ClientMessage::Command {
+        state: snapshot.name.clone(),
+        command: Command::TouchKick { number: 10, direction: 315.0, high: 30.0 },
+        // command: Command::FreeKick { number: 10, direction: 315.0, high: 30.0 },
+        // command: Command::PenaltyKick { number: 10, direction: 18.0, high: 35.0 },
+        // command: Command::QuickTap { number: 9 },
+        // command: Command::Scrum,
     }
-
-    result.push_str(&format!("K/10/10/0/30\n"));
-
-    return result;
 }
 
-pub fn set_offside_test(message: &str) -> String {
-    let mut result = String::new();
+pub fn transformation_test(snapshot: &Snapshot) -> ClientMessage {
+    ClientMessage::Command {
+        state: snapshot.name.clone(),
+        command: Command::Transformation { number: 10, distance: 10.0, direction: 0.0, high: 30.0 },
+    }
+}
 
-    for line in message.lines() {
+pub fn set_offside_test(snapshot: &Snapshot) -> ClientMessage {
+    ClientMessage::Command {
+        state: snapshot.name.clone(),
+        command: Command::LineOut { number: 5, home_line: vec![1, 4, 6, 7, 5], away_line: vec![1, 4, 6, 7, 5] },
+    }
+}
 
-        if line.starts_with("set-offside") {
-            let val: &str = line.trim();
-            result.push_str(&format!("{}\n", val));
-            continue;
-        }
+pub fn quick_throw_test(snapshot: &Snapshot) -> ClientMessage {
+    // Throw in quickly when a player is close to the ball, otherwise form the line out
+    let closest = snapshot.players.iter()
+        .filter(|p| p.team == snapshot.team)
+        .map(|p| (((p.x - snapshot.ball.x).powi(2) + (p.y - snapshot.ball.y).powi(2)).sqrt(), p.number))
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+    match closest {
+        Some((distance, number)) if distance < 10.0 => ClientMessage::Command {
+            state: snapshot.name.clone(),
+            command: Command::QuickThrow {
+                number,
+                x: snapshot.ball.x,
+                angle: if snapshot.ball.y <= 1.0 { 90.0 } else { 270.0 },
+            },
+        },
+        _ => set_offside_test(snapshot),
+    }
+}
 
-        if line.trim().is_empty() {
-            continue;
-        }
+pub fn mark_test(snapshot: &Snapshot) -> ClientMessage {
+    // The player who called the mark kicks toward the opponents
+    let closest = snapshot.players.iter()
+        .filter(|p| p.team == snapshot.team)
+        .min_by(|a, b| {
+            let da = (a.x - snapshot.ball.x).powi(2) + (a.y - snapshot.ball.y).powi(2);
+            let db = (b.x - snapshot.ball.x).powi(2) + (b.y - snapshot.ball.y).powi(2);
+            da.total_cmp(&db)
+        });
+    let opponents: Vec<f32> = snapshot.players.iter().filter(|p| p.team != snapshot.team).map(|p| p.x).collect();
+    let target = opponents.iter().sum::<f32>() / opponents.len().max(1) as f32;
+    let (number, x) = closest.map(|p| (p.number, p.x)).unwrap_or((15, snapshot.ball.x));
+
+    ClientMessage::Command {
+        state: snapshot.name.clone(),
+        command: Command::FreeKick { number, direction: if target > x { 0.0 } else { 180.0 }, high: 30.0 },
+    }
+}
 
-        if line.starts_with("time:") {
-            continue;
-        }
+pub fn not_straight_test(snapshot: &Snapshot) -> ClientMessage {
+    ClientMessage::Command {
+        state: snapshot.name.clone(),
+        command: Command::Scrum,
     }
+}
 
-    result.push_str(&format!("O/5/1-4-6-7-5/1-4-6-7-5\n"));
+fn stay(snapshot: &Snapshot) -> ClientMessage {
+    let actions = snapshot.players.iter()
+        .map(|p| PlayerAction { team: p.team, number: p.number, action: Action::Stay })
+        .collect();
 
-    return result;
+    ClientMessage::Actions { state: snapshot.name.clone(), actions }
 }
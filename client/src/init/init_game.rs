@@ -1,12 +1,20 @@
+use std::env;
 use std::fs::File;
 use std::io::Read;
 
-pub fn initialize_game() -> String {
+use protocol::{ClientMessage, BOTH_SIDES, PROTOCOL_VERSION};
+
+pub fn initialize_game() -> ClientMessage {
+    // Join a match created by another client instead of creating one
+    if let Some(id) = env::var("MATCH_ID").ok().and_then(|id| id.parse().ok()) {
+        let side = env::var("SIDE").ok().and_then(|s| s.parse().ok());
+        return ClientMessage::Join { version: PROTOCOL_VERSION, id, side };
+    }
+
     let field: String = open_file("asset/field.txt");
     let state: String = open_file("asset/state.txt");
     let mut home: Vec<String> = vec![];
     let mut away: Vec<String> = vec![];
-    let mut input: String = String::new();
 
     for i in 1..24 {
         let home_player: String = open_file(&format!("asset/home/{}.txt", i));
@@ -15,16 +23,19 @@ pub fn initialize_game() -> String {
         away.push(away_player);
     }
 
-    input.push_str("init\n");
-    input.push_str(&field);
-    input.push_str("\n");
-    input.push_str(&home.join("\n"));
-    input.push_str("\n");
-    input.push_str(&away.join("\n"));
-    input.push_str("\n");
-    input.push_str(&state);
-    println!("Input: {}", input);
-    return input;
+    // H or A to play one team against another client, both teams by default
+    let side: char = env::var("SIDE").ok().and_then(|s| s.parse().ok()).unwrap_or(BOTH_SIDES);
+
+    let input = ClientMessage::Init {
+        version: PROTOCOL_VERSION,
+        field,
+        home_players: home,
+        away_players: away,
+        state,
+        side,
+    };
+    println!("Input: {}", input.serialize());
+    input
 }
 
 pub fn open_file(file_path: &str) -> String {
@@ -36,4 +47,4 @@ pub fn open_file(file_path: &str) -> String {
     // Replace all occurrences of \n with _
     contents = contents.replace('\n', "_");
     return contents;
-}
\ No newline at end of file
+}
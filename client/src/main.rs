@@ -5,6 +5,8 @@ use std::thread;
 use std::time::Duration;
 use std::net::TcpStream;
 
+use protocol::{frame, ClientMessage, FrameReader, ServerMessage, Snapshot, SPECTATOR};
+
 mod init;
 use init::init_game::initialize_game;
 
@@ -12,10 +14,16 @@ mod ai;
 use ai::take_decision::start_test;
 use ai::take_decision::ruck_test;
 use ai::take_decision::scrum_test;
+use ai::take_decision::maul_test;
 use ai::take_decision::offside_test;
 use ai::take_decision::penalty_test;
 use ai::take_decision::transformation_test;
 use ai::take_decision::set_offside_test;
+use ai::take_decision::line_out_test;
+use ai::take_decision::not_straight_test;
+use ai::take_decision::quick_throw_test;
+use ai::take_decision::drop_out_test;
+use ai::take_decision::mark_test;
 
 fn main() {
     // Load environment variables from .env file
@@ -34,86 +42,78 @@ fn main() {
             let input = initialize_game();
 
             // Send init       message to server
-            if let Err(e) = stream.write_all(input.as_bytes()) {
+            if let Err(e) = stream.write_all(&frame::encode(&input.serialize())) {
                 println!("Failed to send message: {}", e);
                 return;
             }
 
             stream.set_nonblocking(true).expect("Failed to set non-blocking");
 
-            let mut buffer = [0; 2500];
+            let mut buffer = [0; 4096];
+            let mut reader = FrameReader::new();
+            // A spectator only prints what happens
+            let mut watching = false;
             // Read response from server
-            loop {
+            'connection: loop {
                 match stream.read(&mut buffer) {
                     Ok(0) => {
                         println!("Server disconnected.");
                         break;
                     }
                     Ok(n) => {
-                        let msg = String::from_utf8_lossy(&buffer[..n]).trim_matches(char::from(0)).to_string();
+                        reader.push(&buffer[..n]);
 
-                        if msg.trim().starts_with("scrum") {
-                            let decision = scrum_test(msg.trim().as_ref());
-                            if let Err(e) = stream.write_all(decision.as_bytes()) {
-                                println!("Failed to send decision: {}", e);
-                                break;
-                            }
-                            continue;
-                        }
-                        if msg.trim().starts_with("ruck") {
-                            let decision = ruck_test(msg.trim().as_ref());
-                            if let Err(e) = stream.write_all(decision.as_bytes()) {
-                                println!("Failed to send decision: {}", e);
-                                break;
-                            }
-                            continue;
-                        }
-                        if msg.trim().starts_with("offside") {
-                            let decision = offside_test(msg.trim().as_ref());
-                            if let Err(e) = stream.write_all(decision.as_bytes()) {
-                                println!("Failed to send decision: {}", e);
-                                break;
-                            }
-                            continue;
-                        }
-                        if msg.trim().starts_with("set-penalty") {
-                            let decision = penalty_test(msg.trim().as_ref());
-                            if let Err(e) = stream.write_all(decision.as_bytes()) {
-                                println!("Failed to send decision: {}", e);
-                                break;
-                            }
-                            continue;
-                        }
-                        if msg.trim().starts_with("set-transformation") {
-                            let decision = transformation_test(msg.trim().as_ref());
-                            if let Err(e) = stream.write_all(decision.as_bytes()) {
-                                println!("Failed to send decision: {}", e);
-                                break;
-                            }
-                            continue;
-                        }
-                        if msg.trim().starts_with("set-offside") {
-                            let decision = set_offside_test(msg.trim().as_ref());
-                            if let Err(e) = stream.write_all(decision.as_bytes()) {
-                                println!("Failed to send decision: {}", e);
-                                break;
-                            }
-                            continue;
-                        }
+                        loop {
+                            let msg = match reader.next_frame() {
+                                Ok(Some(msg)) => msg,
+                                Ok(None) => break,
+                                Err(e) => {
+                                    println!("Corrupted stream from server: {}", e);
+                                    break 'connection;
+                                }
+                            };
+
+                            let decision = match ServerMessage::parse(&msg) {
+                                Ok(ServerMessage::Welcome { version, match_id, side, snapshot }) => {
+                                    println!("Server speaks protocol version {}, match {} as {}", version, match_id, side);
+                                    watching = side == SPECTATOR;
+                                    if watching {
+                                        continue;
+                                    }
+                                    take_decision(&snapshot)
+                                }
+                                Ok(ServerMessage::State(snapshot)) if snapshot.name == "end" => {
+                                    println!("Full time: H {} - {} A", snapshot.home_score, snapshot.away_score);
+                                    break 'connection;
+                                }
+                                Ok(ServerMessage::State(snapshot)) if watching => {
+                                    for event in snapshot.events {
+                                        println!("{:.2} {}", (snapshot.time as f32) / 100.0, event);
+                                    }
+                                    continue;
+                                }
+                                Ok(ServerMessage::State(snapshot)) => take_decision(&snapshot),
+                                Ok(ServerMessage::Matches(matches)) => {
+                                    for info in matches {
+                                        println!("Match {}", info.serialize());
+                                    }
+                                    continue;
+                                }
+                                Ok(ServerMessage::Error { reason }) => {
+                                    // The server plays on without us, nothing to answer
+                                    println!("Server refused the last message: {}", reason);
+                                    continue;
+                                }
+                                Err(e) => {
+                                    println!("Receive an invalid message: {}", e);
+                                    continue;
+                                }
+                            };
 
-                        if msg.trim().len() == 0 {
-                            let decision = "empty";
-                            println!("Receive an empty message");
-                            if let Err(e) = stream.write_all(decision.as_bytes()) {
+                            if let Err(e) = stream.write_all(&frame::encode(&decision.serialize())) {
                                 println!("Failed to send decision: {}", e);
-                                break;
+                                break 'connection;
                             }
-                            continue;
-                        }
-                        let decision = start_test(msg.trim().as_ref());
-                        if let Err(e) = stream.write_all(decision.as_bytes()) {
-                            println!("Failed to send decision: {}", e);
-                            break;
                         }
                     }
                     Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
@@ -132,3 +132,21 @@ fn main() {
         }
     }
 }
+
+fn take_decision(snapshot: &Snapshot) -> ClientMessage {
+    match snapshot.name.as_str() {
+        "scrum" => scrum_test(snapshot),
+        "ruck" => ruck_test(snapshot),
+        "maul" => maul_test(snapshot),
+        "drop-out" => drop_out_test(snapshot),
+        "offside" => offside_test(snapshot),
+        "set-penalty" => penalty_test(snapshot),
+        "set-transformation" => transformation_test(snapshot),
+        "set-offside" => set_offside_test(snapshot),
+        "set-line_out" => quick_throw_test(snapshot),
+        "line_out" => line_out_test(snapshot),
+        "set-not-straight" => not_straight_test(snapshot),
+        "set-free-kick" => mark_test(snapshot),
+        _ => start_test(snapshot),
+    }
+}
@@ -0,0 +1,94 @@
+use crate::error::{value, ParseError};
+
+/// Order given to a single player for one tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Run { direction: f32 },
+    Walk { direction: f32 },
+    Tackle,
+    Take,
+    Try,
+    Kick { direction: f32, high: f32 },
+    Pass { direction: f32 },
+    Throw { angle: f32 },
+    Jump,
+    Lift,
+    Mark,
+    Stay,
+}
+
+impl Action {
+    /// Parse an action code such as `R90` or `K315/30`.
+    /// `T` is a tackle in open play, a take in scrum and ruck, and a throw in line out.
+    pub fn parse(state: &str, code: &str) -> Result<Action, ParseError> {
+        let code = code.trim();
+        let mut chars = code.chars();
+        let kind = chars.next().ok_or(ParseError::Empty)?;
+        let args: Vec<&str> = chars.as_str().split('/').collect();
+
+        match kind {
+            'R' => Ok(Action::Run { direction: value(args[0])? }),
+            'W' => Ok(Action::Walk { direction: value(args[0])? }),
+            'T' => match state {
+                "line_out" => Ok(Action::Throw { angle: value(args[0])? }),
+                "scrum" | "ruck" => Ok(Action::Take),
+                _ => Ok(Action::Tackle),
+            },
+            'G' => Ok(Action::Try),
+            'K' => Ok(Action::Kick {
+                direction: value(args[0])?,
+                high: value(args.get(1).ok_or(ParseError::MissingLine("kick height"))?)?,
+            }),
+            'P' => Ok(Action::Pass { direction: value(args[0])? }),
+            'J' => Ok(Action::Jump),
+            'L' => Ok(Action::Lift),
+            'M' => Ok(Action::Mark),
+            'S' => Ok(Action::Stay),
+            _ => Err(ParseError::UnknownAction(code.to_string())),
+        }
+    }
+
+    pub fn serialize(&self) -> String {
+        match self {
+            Action::Run { direction } => format!("R{}", direction),
+            Action::Walk { direction } => format!("W{}", direction),
+            Action::Tackle | Action::Take => "T".to_string(),
+            Action::Try => "G".to_string(),
+            Action::Kick { direction, high } => format!("K{}/{}", direction, high),
+            Action::Pass { direction } => format!("P{}", direction),
+            Action::Throw { angle } => format!("T{}", angle),
+            Action::Jump => "J".to_string(),
+            Action::Lift => "L".to_string(),
+            Action::Mark => "M".to_string(),
+            Action::Stay => "S".to_string(),
+        }
+    }
+}
+
+/// One `H10:K315/30` line of an action message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayerAction {
+    pub team: char,
+    pub number: usize,
+    pub action: Action,
+}
+
+impl PlayerAction {
+    pub fn parse(state: &str, line: &str) -> Result<PlayerAction, ParseError> {
+        let (player, action) = line.split_once(':')
+            .ok_or_else(|| ParseError::UnknownAction(line.to_string()))?;
+        let player = player.trim();
+        let team = player.chars().next().ok_or(ParseError::Empty)?;
+        if team != 'H' && team != 'A' {
+            return Err(ParseError::InvalidValue(player.to_string()));
+        }
+        let number = value(&player[1..])?;
+        let action = Action::parse(state, action)?;
+
+        Ok(PlayerAction { team, number, action })
+    }
+
+    pub fn serialize(&self) -> String {
+        format!("{}{}:{}", self.team, self.number, self.action.serialize())
+    }
+}
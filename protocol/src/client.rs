@@ -0,0 +1,233 @@
+use crate::action::PlayerAction;
+use crate::command::Command;
+use crate::substitution::Substitution;
+use crate::error::{value, ParseError};
+
+/// Default squad size when the `init` header does not carry one.
+pub const DEFAULT_SQUAD_SIZE: usize = 23;
+/// Side of a client controlling both teams, `H` or `A` control one team each.
+pub const BOTH_SIDES: char = 'B';
+/// Side of a client watching a match without playing.
+pub const SPECTATOR: char = 'S';
+/// States answered with player actions, the `set-` states are answered with a command.
+pub const PLAY_STATES: [&str; 13] = [
+    "start", "play", "restart", "drop-out", "free-kick", "touch-kick", "penalty-kick", "transformation-kick",
+    "scrum", "ruck", "maul", "line_out", "half-time",
+];
+
+/// Everything a client can send to the server.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientMessage {
+    Init {
+        version: u32,
+        field: String,
+        home_players: Vec<String>,
+        away_players: Vec<String>,
+        state: String,
+        side: char,
+    },
+    Actions {
+        state: String,
+        actions: Vec<PlayerAction>,
+    },
+    Command {
+        state: String,
+        command: Command,
+    },
+    Substitute {
+        substitutions: Vec<Substitution>,
+    },
+    /// Ask the lobby for the matches in progress.
+    List,
+    /// Take a seat in an existing match, the first free one when no side is given.
+    Join {
+        version: u32,
+        id: u32,
+        side: Option<char>,
+    },
+}
+
+impl ClientMessage {
+    pub fn parse(input: &str) -> Result<ClientMessage, ParseError> {
+        let input = input.trim_matches(char::from(0)).trim();
+        let mut lines = input.split('\n').map(|line| line.trim_end_matches('\r'));
+        let header = lines.next().unwrap_or("").trim();
+        let mut words = header.split(' ');
+        let state = words.next().unwrap_or("");
+
+        match state {
+            "" => Err(ParseError::Empty),
+            "list" => Ok(ClientMessage::List),
+            "join" => {
+                let version = value(words.next().ok_or(ParseError::MissingLine("version"))?)?;
+                let id = value(words.next().ok_or(ParseError::MissingLine("match id"))?)?;
+                let side = words.next().map(value).transpose()?;
+                if side.is_some_and(|s| s != 'H' && s != 'A' && s != SPECTATOR) {
+                    return Err(ParseError::InvalidValue(header.to_string()));
+                }
+
+                Ok(ClientMessage::Join { version, id, side })
+            },
+            "sub" => {
+                let substitutions = lines
+                    .filter(|l| !l.trim().is_empty())
+                    .map(Substitution::parse)
+                    .collect::<Result<Vec<Substitution>, ParseError>>()?;
+
+                Ok(ClientMessage::Substitute { substitutions })
+            },
+            "init" => {
+                let version = value(words.next().ok_or(ParseError::MissingLine("version"))?)?;
+                let home_count = words.next().map(value).transpose()?.unwrap_or(DEFAULT_SQUAD_SIZE);
+                let away_count = words.next().map(value).transpose()?.unwrap_or(DEFAULT_SQUAD_SIZE);
+                let side = words.next().map(value).transpose()?.unwrap_or(BOTH_SIDES);
+                if side != 'H' && side != 'A' && side != BOTH_SIDES {
+                    return Err(ParseError::InvalidValue(side.to_string()));
+                }
+
+                let field = lines.next().ok_or(ParseError::MissingLine("field"))?.to_string();
+                let home_players: Vec<String> = lines.by_ref().take(home_count).map(|l| l.to_string()).collect();
+                let away_players: Vec<String> = lines.by_ref().take(away_count).map(|l| l.to_string()).collect();
+                if home_players.len() != home_count || away_players.len() != away_count {
+                    return Err(ParseError::MissingLine("player"));
+                }
+                let state = lines.next().ok_or(ParseError::MissingLine("state"))?.to_string();
+
+                Ok(ClientMessage::Init { version, field, home_players, away_players, state, side })
+            },
+            _ if state.starts_with("set-") => {
+                let line = lines.find(|l| !l.trim().is_empty()).ok_or(ParseError::MissingLine("command"))?;
+                let command = Command::parse(state, line)?;
+
+                Ok(ClientMessage::Command { state: state.to_string(), command })
+            },
+            _ if PLAY_STATES.contains(&state) => {
+                let actions = lines
+                    .filter(|l| !l.trim().is_empty())
+                    .map(|l| PlayerAction::parse(state, l))
+                    .collect::<Result<Vec<PlayerAction>, ParseError>>()?;
+
+                Ok(ClientMessage::Actions { state: state.to_string(), actions })
+            },
+            _ => Err(ParseError::UnknownMessage(header.to_string())),
+        }
+    }
+
+    pub fn serialize(&self) -> String {
+        match self {
+            ClientMessage::Init { version, field, home_players, away_players, state, side } => {
+                let mut result = format!("init {} {} {} {}\n", version, home_players.len(), away_players.len(), side);
+                result.push_str(field);
+                result.push('\n');
+                for player in home_players.iter().chain(away_players.iter()) {
+                    result.push_str(player);
+                    result.push('\n');
+                }
+                result.push_str(state);
+                result
+            },
+            ClientMessage::Actions { state, actions } => {
+                let mut result = state.clone();
+                for action in actions {
+                    result.push('\n');
+                    result.push_str(&action.serialize());
+                }
+                result
+            },
+            ClientMessage::Command { state, command } => format!("{}\n{}", state, command.serialize()),
+            ClientMessage::Substitute { substitutions } => {
+                let mut result = "sub".to_string();
+                for substitution in substitutions {
+                    result.push('\n');
+                    result.push_str(&substitution.serialize());
+                }
+                result
+            },
+            ClientMessage::List => "list".to_string(),
+            ClientMessage::Join { version, id, side: Some(side) } => format!("join {} {} {}", version, id, side),
+            ClientMessage::Join { version, id, side: None } => format!("join {} {}", version, id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+
+    fn round_trip(message: ClientMessage) {
+        assert_eq!(ClientMessage::parse(&message.serialize()), Ok(message));
+    }
+
+    #[test]
+    fn init_round_trip() {
+        round_trip(ClientMessage::Init {
+            version: crate::PROTOCOL_VERSION,
+            field: "fw=100_fh=70".to_string(),
+            home_players: vec!["s=185_p=125".to_string(), "s=180_p=100".to_string()],
+            away_players: vec!["s=200_p=115".to_string()],
+            state: "set-penalty H 80 26".to_string(),
+            side: 'H',
+        });
+    }
+
+    #[test]
+    fn actions_round_trip() {
+        round_trip(ClientMessage::Actions {
+            state: "play".to_string(),
+            actions: vec![
+                PlayerAction { team: 'H', number: 10, action: Action::Kick { direction: 315.5, high: 30.0 } },
+                PlayerAction { team: 'H', number: 9, action: Action::Pass { direction: 90.0 } },
+                PlayerAction { team: 'A', number: 7, action: Action::Tackle },
+                PlayerAction { team: 'A', number: 1, action: Action::Stay },
+            ],
+        });
+        round_trip(ClientMessage::Actions { state: "ruck".to_string(), actions: vec![] });
+    }
+
+    #[test]
+    fn command_round_trip() {
+        let commands = [
+            ("set-penalty", Command::PenaltyKick { number: 10, direction: 12.5, high: 40.0 }),
+            ("set-penalty", Command::TouchKick { number: 15, direction: 315.0, high: 30.0 }),
+            ("set-penalty", Command::QuickTap { number: 9 }),
+            ("set-free-kick", Command::FreeKick { number: 10, direction: 0.0, high: 45.0 }),
+            ("set-not-straight", Command::Scrum),
+            ("set-transformation", Command::Transformation { number: 10, distance: 10.0, direction: 135.0, high: 30.0 }),
+            ("set-line_out", Command::LineOut { number: 5, home_line: vec![1, 4, 6, 7, 5], away_line: vec![4, 5] }),
+            ("set-line_out", Command::QuickThrow { number: 11, x: 60.0, angle: 90.0 }),
+        ];
+        for (state, command) in commands {
+            round_trip(ClientMessage::Command { state: state.to_string(), command });
+        }
+    }
+
+    #[test]
+    fn substitute_round_trip() {
+        round_trip(ClientMessage::Substitute {
+            substitutions: vec![
+                Substitution { team: 'H', off: 9, on: 22 },
+                Substitution { team: 'A', off: 1, on: 17 },
+            ],
+        });
+    }
+
+    #[test]
+    fn lobby_round_trip() {
+        round_trip(ClientMessage::List);
+        round_trip(ClientMessage::Join { version: crate::PROTOCOL_VERSION, id: 3, side: Some('A') });
+        round_trip(ClientMessage::Join { version: crate::PROTOCOL_VERSION, id: 3, side: Some(SPECTATOR) });
+        round_trip(ClientMessage::Join { version: crate::PROTOCOL_VERSION, id: 3, side: None });
+    }
+
+    #[test]
+    fn unknown_header_is_refused() {
+        assert_eq!(ClientMessage::parse("hello\nH10:S"), Err(ParseError::UnknownMessage("hello".to_string())));
+        assert_eq!(ClientMessage::parse(""), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn init_needs_a_version() {
+        assert_eq!(ClientMessage::parse("init"), Err(ParseError::MissingLine("version")));
+    }
+}
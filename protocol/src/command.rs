@@ -0,0 +1,92 @@
+use crate::error::{value, ParseError};
+
+/// Decision sent by a client while the game waits in a `set-*` state.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    PenaltyKick { number: usize, direction: f32, high: f32 },
+    FreeKick { number: usize, direction: f32, high: f32 },
+    TouchKick { number: usize, direction: f32, high: f32 },
+    QuickTap { number: usize },
+    Scrum,
+    Transformation { number: usize, distance: f32, direction: f32, high: f32 },
+    LineOut { number: usize, home_line: Vec<usize>, away_line: Vec<usize> },
+    QuickThrow { number: usize, x: f32, angle: f32 },
+}
+
+impl Command {
+    /// Parse a command line such as `K/10/315/30`.
+    /// `K` is a free kick after a penalty or a mark and the kick itself in a transformation.
+    /// A penalty is also kicked at goal with `P`, to touch with `T` (throw kept), tapped with `Q/9` or taken as a scrum `S`.
+    /// `Q/11/60/90` is a quick throw-in by player 11 at x 60 before the line out forms.
+    /// After a throw not straight the opponents pick `S` for a scrum or `O` for their own line out.
+    pub fn parse(state: &str, line: &str) -> Result<Command, ParseError> {
+        let line = line.trim();
+        let mut chars = line.chars();
+        let kind = chars.next().ok_or(ParseError::Empty)?;
+        let args: Vec<&str> = chars.as_str().trim_start_matches('/').split('/').collect();
+        let arg = |index: usize| -> Result<&str, ParseError> {
+            args.get(index).copied().ok_or_else(|| ParseError::InvalidValue(line.to_string()))
+        };
+
+        match (state, kind) {
+            ("set-penalty", 'P') => Ok(Command::PenaltyKick {
+                number: value(arg(0)?)?,
+                direction: value(arg(1)?)?,
+                high: value(arg(2)?)?,
+            }),
+            ("set-penalty", 'K') | ("set-free-kick", 'K') => Ok(Command::FreeKick {
+                number: value(arg(0)?)?,
+                direction: value(arg(1)?)?,
+                high: value(arg(2)?)?,
+            }),
+            ("set-penalty", 'T') => Ok(Command::TouchKick {
+                number: value(arg(0)?)?,
+                direction: value(arg(1)?)?,
+                high: value(arg(2)?)?,
+            }),
+            ("set-penalty", 'Q') => Ok(Command::QuickTap { number: value(arg(0)?)? }),
+            ("set-penalty", 'S') | ("set-not-straight", 'S') | ("set-free-kick", 'S') => Ok(Command::Scrum),
+            ("set-transformation", 'K') => Ok(Command::Transformation {
+                number: value(arg(0)?)?,
+                distance: value(arg(1)?)?,
+                direction: value(arg(2)?)?,
+                high: value(arg(3)?)?,
+            }),
+            ("set-line_out", 'Q') => Ok(Command::QuickThrow {
+                number: value(arg(0)?)?,
+                x: value(arg(1)?)?,
+                angle: value(arg(2)?)?,
+            }),
+            (_, 'O') => Ok(Command::LineOut {
+                number: value(arg(0)?)?,
+                home_line: line_up(arg(1)?)?,
+                away_line: line_up(arg(2)?)?,
+            }),
+            _ => Err(ParseError::UnknownCommand(line.to_string())),
+        }
+    }
+
+    pub fn serialize(&self) -> String {
+        match self {
+            Command::PenaltyKick { number, direction, high } => format!("P/{}/{}/{}", number, direction, high),
+            Command::FreeKick { number, direction, high } => format!("K/{}/{}/{}", number, direction, high),
+            Command::TouchKick { number, direction, high } => format!("T/{}/{}/{}", number, direction, high),
+            Command::QuickTap { number } => format!("Q/{}", number),
+            Command::Scrum => "S".to_string(),
+            Command::Transformation { number, distance, direction, high } => {
+                format!("K/{}/{}/{}/{}", number, distance, direction, high)
+            },
+            Command::LineOut { number, home_line, away_line } => format!(
+                "O/{}/{}/{}",
+                number,
+                home_line.iter().map(|n| n.to_string()).collect::<Vec<String>>().join("-"),
+                away_line.iter().map(|n| n.to_string()).collect::<Vec<String>>().join("-")
+            ),
+            Command::QuickThrow { number, x, angle } => format!("Q/{}/{}/{}", number, x, angle),
+        }
+    }
+}
+
+fn line_up(text: &str) -> Result<Vec<usize>, ParseError> {
+    text.split('-').map(value).collect()
+}
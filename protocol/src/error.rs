@@ -0,0 +1,45 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    Empty,
+    UnknownMessage(String),
+    UnknownAction(String),
+    UnknownCommand(String),
+    InvalidValue(String),
+    MissingLine(&'static str),
+    UnsupportedVersion(u32),
+    FrameTooLarge(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty message"),
+            ParseError::UnknownMessage(header) => write!(f, "unknown message \"{}\"", header),
+            ParseError::UnknownAction(action) => write!(f, "unknown action \"{}\"", action),
+            ParseError::UnknownCommand(command) => write!(f, "unknown command \"{}\"", command),
+            ParseError::InvalidValue(value) => write!(f, "invalid value \"{}\"", value),
+            ParseError::MissingLine(what) => write!(f, "missing {}", what),
+            ParseError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported protocol version {} (server speaks {})",
+                version,
+                crate::PROTOCOL_VERSION
+            ),
+            ParseError::FrameTooLarge(size) => write!(
+                f,
+                "frame of {} bytes exceeds the {} bytes limit",
+                size,
+                crate::frame::MAX_FRAME_SIZE
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a single value, keeping the offending text in the error.
+pub(crate) fn value<T: std::str::FromStr>(text: &str) -> Result<T, ParseError> {
+    text.trim().parse().map_err(|_| ParseError::InvalidValue(text.to_string()))
+}
@@ -0,0 +1,93 @@
+use crate::error::ParseError;
+
+/// Size of the big-endian length written before every message.
+pub const HEADER_SIZE: usize = 4;
+/// Largest message accepted, anything bigger is treated as a corrupted stream.
+pub const MAX_FRAME_SIZE: usize = 1024 * 1024;
+
+/// Prefix a serialized message with its length so it can cross TCP in one piece.
+pub fn encode(message: &str) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(HEADER_SIZE + message.len());
+    frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    frame.extend_from_slice(message.as_bytes());
+    frame
+}
+
+/// Reassembles frames from whatever chunks the socket hands us.
+#[derive(Default)]
+pub struct FrameReader {
+    buffer: Vec<u8>,
+}
+
+impl FrameReader {
+    pub fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    /// Append bytes freshly read from the stream.
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Pop the next complete message, or `None` until enough bytes arrived.
+    pub fn next_frame(&mut self) -> Result<Option<String>, ParseError> {
+        if self.buffer.len() < HEADER_SIZE {
+            return Ok(None);
+        }
+        let mut header = [0; HEADER_SIZE];
+        header.copy_from_slice(&self.buffer[..HEADER_SIZE]);
+        let size = u32::from_be_bytes(header) as usize;
+        if size > MAX_FRAME_SIZE {
+            return Err(ParseError::FrameTooLarge(size));
+        }
+        if self.buffer.len() < HEADER_SIZE + size {
+            return Ok(None);
+        }
+
+        let frame: Vec<u8> = self.buffer.drain(..HEADER_SIZE + size).skip(HEADER_SIZE).collect();
+        Ok(Some(String::from_utf8_lossy(&frame).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_split_over_reads() {
+        let frame = encode("play H\ntime:25");
+        let mut reader = FrameReader::new();
+        for byte in &frame[..frame.len() - 1] {
+            reader.push(&[*byte]);
+            assert_eq!(reader.next_frame(), Ok(None));
+        }
+        reader.push(&frame[frame.len() - 1..]);
+        assert_eq!(reader.next_frame(), Ok(Some("play H\ntime:25".to_string())));
+        assert_eq!(reader.next_frame(), Ok(None));
+    }
+
+    #[test]
+    fn frames_coalesced_in_one_read() {
+        let mut data = encode("list");
+        data.extend(encode(""));
+        data.extend(encode("join 1 A"));
+        let mut reader = FrameReader::new();
+        reader.push(&data[..data.len() - 2]);
+        assert_eq!(reader.next_frame(), Ok(Some("list".to_string())));
+        assert_eq!(reader.next_frame(), Ok(Some("".to_string())));
+        assert_eq!(reader.next_frame(), Ok(None));
+        reader.push(&data[data.len() - 2..]);
+        assert_eq!(reader.next_frame(), Ok(Some("join 1 A".to_string())));
+    }
+
+    #[test]
+    fn oversize_frame_is_refused() {
+        let mut reader = FrameReader::new();
+        reader.push(&((MAX_FRAME_SIZE + 1) as u32).to_be_bytes());
+        assert_eq!(reader.next_frame(), Err(ParseError::FrameTooLarge(MAX_FRAME_SIZE + 1)));
+
+        let mut reader = FrameReader::new();
+        reader.push(&(MAX_FRAME_SIZE as u32).to_be_bytes());
+        assert_eq!(reader.next_frame(), Ok(None));
+    }
+}
@@ -0,0 +1,43 @@
+pub mod error;
+pub mod action;
+pub mod command;
+pub mod substitution;
+pub mod client;
+pub mod server;
+pub mod frame;
+
+pub use error::ParseError;
+pub use action::{Action, PlayerAction};
+pub use command::Command;
+pub use substitution::Substitution;
+pub use client::{ClientMessage, BOTH_SIDES, PLAY_STATES, SPECTATOR};
+pub use server::{ServerMessage, Snapshot, PlayerState, Position, Card, RuckState, MatchInfo};
+pub use frame::FrameReader;
+
+/// Version spoken by this build of the protocol.
+/// 2: every message is length-prefixed, see `frame`.
+/// 3: snapshots carry the score, the match ends with an `end` state.
+/// 4: clients can replace players with a `sub` message during dead ball.
+/// 5: a line out thrown not straight gives the opponents a `set-not-straight` option.
+/// 6: the team awarded a line out can take a quick throw-in with `Q` in `set-line_out`.
+/// 7: a ball dead in-goal restarts with a `drop-out` state, answered with player actions.
+/// 8: a player catching a kick in their own 22 can call a mark `M`, answered in `set-free-kick`.
+/// 9: snapshots list yellow and red cards with `card:` lines, older clients cannot read them.
+/// 10: a penalty can be kicked to touch `T` keeping the throw, or tapped `Q` to play on.
+/// 11: during a ruck snapshots carry a `ruck:` line with how clear the ball is and the use-it time left.
+/// 12: `init` carries the side the client plays, `H` or `A` pairs it with a client of the other side.
+/// 13: a lobby lists matches with `list` and seats clients with `join`, `welcome` names the match and the side.
+/// 14: snapshots list what happened during the tick with `event:` lines, older clients cannot read them.
+/// 15: the server plays every tick by a deadline, `empty` is gone and an error needs no answer.
+/// 16: `join` carries the version of the client, `init` always creates a new match.
+pub const PROTOCOL_VERSION: u32 = 16;
+
+/// Pick the version both sides will speak, or fail if the client speaks another one.
+/// Every version changed the wire format, so only the current one is accepted.
+pub fn negotiate_version(requested: u32) -> Result<u32, ParseError> {
+    if requested == PROTOCOL_VERSION {
+        Ok(requested)
+    } else {
+        Err(ParseError::UnsupportedVersion(requested))
+    }
+}
@@ -0,0 +1,337 @@
+use crate::error::{value, ParseError};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayerState {
+    pub team: char,
+    pub number: usize,
+    pub x: f32,
+    pub y: f32,
+    pub has_ball: bool,
+}
+
+/// Card shown by the referee: `Y` until the sin bin time ends, `R` for the rest of the match.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Card {
+    pub team: char,
+    pub number: usize,
+    pub kind: char,
+    pub until: u64, // 0 for a red card
+}
+
+/// Contest over the ball during a ruck: `clear` is the share of the weight over the ball
+/// on the side of the team in possession, `use_it` the centiseconds left once the referee called it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RuckState {
+    pub clear: f32,
+    pub use_it: u64,
+}
+
+/// State of the game sent after every tick.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    pub name: String,
+    pub team: char,
+    pub time: u64,
+    pub home_score: u32,
+    pub away_score: u32,
+    pub ball: Position,
+    pub players: Vec<PlayerState>,
+    pub cards: Vec<Card>,
+    pub ruck: Option<RuckState>,
+    pub events: Vec<String>,
+}
+
+/// One line of the lobby: which seats of a match are taken and how many watch it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchInfo {
+    pub id: u32,
+    pub status: String,
+    pub home: bool,
+    pub away: bool,
+    pub spectators: usize,
+}
+
+/// Everything the server can send to a client.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ServerMessage {
+    Welcome { version: u32, match_id: u32, side: char, snapshot: Snapshot },
+    State(Snapshot),
+    Matches(Vec<MatchInfo>),
+    Error { reason: String },
+}
+
+impl ServerMessage {
+    pub fn parse(input: &str) -> Result<ServerMessage, ParseError> {
+        let input = input.trim_matches(char::from(0)).trim();
+        let (header, rest) = input.split_once('\n').unwrap_or((input, ""));
+        let mut words = header.trim().split(' ');
+
+        match words.next().unwrap_or("") {
+            "" => Err(ParseError::Empty),
+            "welcome" => {
+                let version = value(words.next().ok_or(ParseError::MissingLine("version"))?)?;
+                let match_id = words.next().map(value).transpose()?.unwrap_or(0);
+                let side = words.next().map(value).transpose()?.unwrap_or('B');
+                Ok(ServerMessage::Welcome { version, match_id, side, snapshot: Snapshot::parse(rest)? })
+            },
+            "matches" => {
+                let matches = rest.lines()
+                    .filter(|l| !l.trim().is_empty())
+                    .map(MatchInfo::parse)
+                    .collect::<Result<Vec<MatchInfo>, ParseError>>()?;
+                Ok(ServerMessage::Matches(matches))
+            },
+            "error" => Ok(ServerMessage::Error {
+                reason: header.trim()["error".len()..].trim().to_string(),
+            }),
+            _ => Ok(ServerMessage::State(Snapshot::parse(input)?)),
+        }
+    }
+
+    pub fn serialize(&self) -> String {
+        match self {
+            ServerMessage::Welcome { version, match_id, side, snapshot } => {
+                format!("welcome {} {} {}\n{}", version, match_id, side, snapshot.serialize())
+            },
+            ServerMessage::State(snapshot) => snapshot.serialize(),
+            ServerMessage::Matches(matches) => {
+                let mut result = "matches".to_string();
+                for info in matches {
+                    result.push('\n');
+                    result.push_str(&info.serialize());
+                }
+                result
+            },
+            ServerMessage::Error { reason } => format!("error {}", reason),
+        }
+    }
+}
+
+impl MatchInfo {
+    /// `3 playing HA 1`: match 3 is played, home and away seats taken, one spectator.
+    pub fn parse(line: &str) -> Result<MatchInfo, ParseError> {
+        let invalid = || ParseError::InvalidValue(line.to_string());
+        let mut words = line.split_whitespace();
+        let id = value(words.next().ok_or_else(invalid)?)?;
+        let status = words.next().ok_or_else(invalid)?.to_string();
+        let seats = words.next().ok_or_else(invalid)?;
+        let spectators = value(words.next().ok_or_else(invalid)?)?;
+        Ok(MatchInfo { id, status, home: seats.contains('H'), away: seats.contains('A'), spectators })
+    }
+
+    pub fn serialize(&self) -> String {
+        let home = if self.home { "H" } else { "-" };
+        let away = if self.away { "A" } else { "-" };
+        format!("{} {} {}{} {}", self.id, self.status, home, away, self.spectators)
+    }
+}
+
+impl Snapshot {
+    pub fn parse(input: &str) -> Result<Snapshot, ParseError> {
+        let mut lines = input.trim().lines();
+        let header = lines.next().ok_or(ParseError::MissingLine("state"))?;
+        let mut words = header.trim().split(' ');
+        let name = words.next().unwrap_or("").to_string();
+        let team = words.next().and_then(|s| s.chars().next()).unwrap_or('H');
+        let mut snapshot = Snapshot {
+            name,
+            team,
+            time: 0,
+            home_score: 0,
+            away_score: 0,
+            ball: Position { x: 0.0, y: 0.0 },
+            players: Vec::new(),
+            cards: Vec::new(),
+            ruck: None,
+            events: Vec::new(),
+        };
+
+        for line in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, info) = line.split_once(':').ok_or_else(|| ParseError::InvalidValue(line.to_string()))?;
+            match key {
+                "time" => snapshot.time = value(info)?,
+                "score" => {
+                    let (home, away) = info.split_once('-').ok_or_else(|| ParseError::InvalidValue(info.to_string()))?;
+                    snapshot.home_score = value(home)?;
+                    snapshot.away_score = value(away)?;
+                },
+                "B" => snapshot.ball = position(info)?,
+                "card" => snapshot.cards.push(card(info)?),
+                "event" => snapshot.events.push(info.trim().to_string()),
+                "ruck" => {
+                    let (clear, use_it) = info.trim().split_once(' ').ok_or_else(|| ParseError::InvalidValue(info.to_string()))?;
+                    snapshot.ruck = Some(RuckState { clear: value(clear)?, use_it: value(use_it)? });
+                },
+                _ => {
+                    let (team, number) = player(key)?;
+                    let (player, ball) = match info.split_once("/B:") {
+                        Some((player, ball)) => (player, Some(ball)),
+                        None => (info, None),
+                    };
+                    let player = position(player)?;
+                    if let Some(ball) = ball {
+                        snapshot.ball = position(ball)?;
+                    }
+                    snapshot.players.push(PlayerState { team, number, x: player.x, y: player.y, has_ball: ball.is_some() });
+                },
+            }
+        }
+        Ok(snapshot)
+    }
+
+    pub fn serialize(&self) -> String {
+        let mut result = format!("{} {}\n", self.name, self.team);
+        result.push_str(&format!("time:{}\n", self.time));
+        result.push_str(&format!("score:{}-{}\n", self.home_score, self.away_score));
+        if !self.players.iter().any(|p| p.has_ball) {
+            result.push_str(&format!("B: {} {}\n", self.ball.x, self.ball.y));
+        }
+        for player in &self.players {
+            result.push_str(&format!("{}{}: {} {}", player.team, player.number, player.x, player.y));
+            if player.has_ball {
+                result.push_str(&format!("/B: {} {}", self.ball.x, self.ball.y));
+            }
+            result.push('\n');
+        }
+        if let Some(ruck) = &self.ruck {
+            result.push_str(&format!("ruck:{} {}\n", ruck.clear, ruck.use_it));
+        }
+        for card in &self.cards {
+            result.push_str(&format!("card:{}{} {} {}\n", card.team, card.number, card.kind, card.until));
+        }
+        for event in &self.events {
+            result.push_str(&format!("event:{}\n", event));
+        }
+        result.trim_end().to_string()
+    }
+
+    /// What happened since `previous`, as listed by the `event:` lines:
+    /// `state ruck H` on a new state, `score H 5` on points scored, `card H7 Y` on a card shown,
+    /// `card H7 R` for a red card, shown directly or for a second yellow.
+    pub fn events_since(&self, previous: &Snapshot) -> Vec<String> {
+        let mut events = Vec::new();
+        if self.name != previous.name || self.team != previous.team {
+            events.push(format!("state {} {}", self.name, self.team));
+        }
+        for (team, score, before) in [('H', self.home_score, previous.home_score), ('A', self.away_score, previous.away_score)] {
+            if score > before {
+                events.push(format!("score {} {}", team, score - before));
+            }
+        }
+        for card in &self.cards {
+            if !previous.cards.iter().any(|c| c.team == card.team && c.number == card.number && c.kind == card.kind) {
+                events.push(format!("card {}{} {}", card.team, card.number, card.kind));
+            }
+        }
+        events
+    }
+
+    /// The player currently holding the ball, if any.
+    pub fn carrier(&self) -> Option<&PlayerState> {
+        self.players.iter().find(|p| p.has_ball)
+    }
+}
+
+fn position(text: &str) -> Result<Position, ParseError> {
+    let mut coords = text.split_whitespace();
+    let x = value(coords.next().ok_or_else(|| ParseError::InvalidValue(text.to_string()))?)?;
+    let y = value(coords.next().ok_or_else(|| ParseError::InvalidValue(text.to_string()))?)?;
+    Ok(Position { x, y })
+}
+
+// `H7`: the team letter then the number of a player
+fn player(text: &str) -> Result<(char, usize), ParseError> {
+    let team = text.chars().next().ok_or(ParseError::Empty)?;
+    let (_, number) = text.split_at(team.len_utf8());
+    Ok((team, value(number)?))
+}
+
+fn card(text: &str) -> Result<Card, ParseError> {
+    let invalid = || ParseError::InvalidValue(text.to_string());
+    let mut words = text.split_whitespace();
+    let (team, number) = player(words.next().ok_or_else(invalid)?)?;
+    let kind = words.next().and_then(|s| s.chars().next()).ok_or_else(invalid)?;
+    let until = value(words.next().ok_or_else(invalid)?)?;
+    Ok(Card { team, number, kind, until })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(message: ServerMessage) {
+        assert_eq!(ServerMessage::parse(&message.serialize()), Ok(message));
+    }
+
+    fn snapshot(carried: bool) -> Snapshot {
+        Snapshot {
+            name: "ruck".to_string(),
+            team: 'A',
+            time: 4525,
+            home_score: 7,
+            away_score: 3,
+            ball: Position { x: 59.5, y: 33.25 },
+            players: vec![
+                PlayerState { team: 'H', number: 10, x: 60.0, y: 30.5, has_ball: false },
+                PlayerState { team: 'A', number: 9, x: 59.5, y: 33.25, has_ball: carried },
+            ],
+            cards: vec![
+                Card { team: 'H', number: 7, kind: 'Y', until: 6000 },
+                Card { team: 'A', number: 3, kind: 'R', until: 0 },
+            ],
+            ruck: Some(RuckState { clear: 0.75, use_it: 300 }),
+            events: vec!["state ruck A".to_string(), "card H7 Y".to_string()],
+        }
+    }
+
+    #[test]
+    fn state_round_trip() {
+        round_trip(ServerMessage::State(snapshot(true)));
+        round_trip(ServerMessage::State(snapshot(false)));
+    }
+
+    #[test]
+    fn welcome_round_trip() {
+        round_trip(ServerMessage::Welcome { version: crate::PROTOCOL_VERSION, match_id: 2, side: 'H', snapshot: snapshot(true) });
+    }
+
+    #[test]
+    fn matches_round_trip() {
+        round_trip(ServerMessage::Matches(vec![
+            MatchInfo { id: 1, status: "playing".to_string(), home: true, away: true, spectators: 2 },
+            MatchInfo { id: 2, status: "waiting".to_string(), home: false, away: true, spectators: 0 },
+        ]));
+        round_trip(ServerMessage::Matches(vec![]));
+    }
+
+    #[test]
+    fn error_round_trip() {
+        round_trip(ServerMessage::Error { reason: "team H already taken in match 1".to_string() });
+    }
+
+    #[test]
+    fn multibyte_player_key_is_an_error() {
+        assert_eq!(ServerMessage::parse("play H\né: 1 2"), Err(ParseError::InvalidValue("".to_string())));
+        assert_eq!(ServerMessage::parse("play H\ncard:é Y 10"), Err(ParseError::InvalidValue("".to_string())));
+    }
+
+    #[test]
+    fn events_since_previous_snapshot() {
+        let previous = snapshot(true);
+        let mut current = previous.clone();
+        current.name = "play".to_string();
+        current.away_score += 5;
+        current.cards.push(Card { team: 'A', number: 1, kind: 'R', until: 0 });
+        assert_eq!(current.events_since(&previous), vec!["state play A", "score A 5", "card A1 R"]);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::error::{value, ParseError};
+
+/// One `H9-22` line of a `sub` message: player 22 replaces player 9.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Substitution {
+    pub team: char,
+    pub off: usize,
+    pub on: usize,
+}
+
+impl Substitution {
+    pub fn parse(line: &str) -> Result<Substitution, ParseError> {
+        let line = line.trim();
+        let mut chars = line.chars();
+        let team = chars.next().ok_or(ParseError::Empty)?;
+        if team != 'H' && team != 'A' {
+            return Err(ParseError::InvalidValue(line.to_string()));
+        }
+        let (off, on) = chars.as_str().split_once('-')
+            .ok_or_else(|| ParseError::InvalidValue(line.to_string()))?;
+
+        Ok(Substitution { team, off: value(off)?, on: value(on)? })
+    }
+
+    pub fn serialize(&self) -> String {
+        format!("{}{}-{}", self.team, self.off, self.on)
+    }
+}
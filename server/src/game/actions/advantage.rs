@@ -0,0 +1,160 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::*;
+
+impl GameState {
+    /// Record an infringement and let the non-offending `team` play on.
+    /// Returns true when the referee stops play straight away.
+    pub fn play_advantage(&mut self, team: char, x: f32, y: f32, sanction: Sanction) -> bool {
+        let open_play = matches!(self.state.name.as_str(), "play" | "ruck" | "maul" | "restart" | "free-kick" | "touch-kick" | "penalty-kick");
+        let advantage = Advantage { team, x, y, until: self.time + self.field.advantage_ticks * 25, sanction };
+
+        match self.advantage {
+            // The side playing advantage offends in turn, back to the first infringement
+            Some(pending) if pending.team != team => {
+                self.award_sanction(pending);
+                true
+            },
+            // A penalty offence on top of a lesser one raises the sanction
+            Some(pending) if pending.sanction != Sanction::Penalty && sanction == Sanction::Penalty => {
+                self.advantage = Some(advantage);
+                print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                println!("Advantage {} now for a penalty", team);
+                false
+            },
+            Some(_) => false,
+            None if !open_play => {
+                self.award_sanction(advantage);
+                true
+            },
+            None => {
+                self.advantage = Some(advantage);
+                print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                println!("Advantage {} for a {:?}", team, sanction);
+                false
+            },
+        }
+    }
+
+    /// Advantage is over once the non-offending team keeps the ball long enough or gains ground,
+    /// otherwise the game comes back to the mark of the infringement.
+    pub fn update_advantage(&mut self) {
+        let Some(advantage) = self.advantage else {
+            return;
+        };
+
+        let holder = if self.ball.is_carried || self.state.name == "ruck" || self.state.name == "maul" { Some(self.state.team) } else { None };
+        let gain = (self.ball.x - advantage.x) * self.attack_sign(advantage.team);
+        let back_to_mark = match self.state.name.as_str() {
+            "play" | "ruck" | "maul" | "restart" | "free-kick" | "touch-kick" | "penalty-kick" => match holder {
+                Some(team) if team != advantage.team => true,
+                Some(_) if self.time >= advantage.until || gain >= ADVANTAGE_GAIN => {
+                    self.advantage = None;
+                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                    println!("Advantage over");
+                    return;
+                },
+                _ => self.time >= advantage.until,
+            },
+            "set-line_out" | "set-not-straight" | "drop-out" => true,
+            _ => {
+                self.advantage = None;
+                return;
+            },
+        };
+
+        if back_to_mark {
+            self.award_sanction(advantage);
+        }
+    }
+
+    /// Stop play and give the non-offending team its restart at the mark.
+    pub fn award_sanction(&mut self, advantage: Advantage) {
+        self.advantage = None;
+        self.ball.is_carried = false;
+        self.ball_throw.active = false;
+        self.ball_throw.rolling = false;
+        self.clear_offside('H');
+        self.clear_offside('A');
+        for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+            player.ball_pos = false;
+        }
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Back to the mark, {:?} for team {}", advantage.sanction, advantage.team);
+        match advantage.sanction {
+            Sanction::Scrum => self.setup_scrum(advantage.team, advantage.x, advantage.y),
+            Sanction::FreeKick => self.setup_free_kick_choice(advantage.team, advantage.x, advantage.y),
+            Sanction::Penalty => self.setup_penalty(advantage.team, advantage.x, advantage.y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::game_state::GameState;
+    use crate::game::models::Sanction;
+
+    // Home fly half carries the ball at x = 60 when the away team gives away a penalty
+    fn advantage_home() -> GameState {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        for player in game.home_team.players.iter_mut().chain(game.away_team.players.iter_mut()) {
+            player.ball_pos = false;
+        }
+        if let Some(p) = game.home_team.players.iter_mut().find(|p| p.number == 10) {
+            p.ball_pos = true;
+        }
+        game.ball.is_carried = true;
+        game.ball.x = 60.0;
+        game.state.team = 'H';
+        assert!(!game.play_advantage('H', 60.0, 30.0, Sanction::Penalty));
+        game
+    }
+
+    #[test]
+    fn ground_gained_ends_the_advantage() {
+        let mut game = advantage_home();
+        game.ball.x = 75.0;
+        game.update_advantage();
+        assert!(game.advantage.is_none());
+        assert_eq!(game.state.name, "play");
+    }
+
+    #[test]
+    fn possession_kept_long_enough_ends_the_advantage() {
+        let mut game = advantage_home();
+        game.time += game.field.advantage_ticks * 25;
+        game.update_advantage();
+        assert!(game.advantage.is_none());
+        assert_eq!(game.state.name, "play");
+    }
+
+    #[test]
+    fn ball_still_loose_in_time_goes_back_to_the_mark() {
+        let mut game = advantage_home();
+        game.ball.is_carried = false;
+        for player in game.home_team.players.iter_mut() {
+            player.ball_pos = false;
+        }
+        game.time += game.field.advantage_ticks * 25;
+        game.update_advantage();
+        assert_eq!(game.state.name, "set-penalty");
+        assert_eq!((game.state.team, game.state.x), ('H', 60.0));
+    }
+
+    #[test]
+    fn turnover_goes_back_to_the_mark() {
+        let mut game = advantage_home();
+        game.state.team = 'A';
+        game.update_advantage();
+        assert_eq!(game.state.name, "set-penalty");
+        assert_eq!(game.state.team, 'H');
+    }
+
+    #[test]
+    fn offence_by_the_side_playing_advantage_goes_back_to_the_first_one() {
+        let mut game = advantage_home();
+        assert!(game.play_advantage('A', 70.0, 30.0, Sanction::Scrum));
+        assert_eq!(game.state.name, "set-penalty");
+        assert_eq!((game.state.team, game.state.x), ('H', 60.0));
+    }
+}
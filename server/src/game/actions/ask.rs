@@ -1,4 +1,6 @@
 use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::QuickThrow;
 
 impl GameState {
     pub fn ask_transformation(&mut self, team: char) {
@@ -45,5 +47,20 @@ impl GameState {
             }
         }
         self.state.team = team;
+
+        // A quick throw-in needs the same ball, the carrier taken into touch still holds it
+        let beyond = if self.state.y == 0.5 { 1.0 - self.ball.y } else { self.ball.y - self.field.height as f32 - 1.0 };
+        let touched_by = self.home_team.players.iter().map(|p| ('H', p))
+            .chain(self.away_team.players.iter().map(|p| ('A', p)))
+            .find(|(_, p)| p.ball_pos)
+            .map(|(team, p)| (team, p.number));
+        self.quick_throw = if beyond < QUICK_THROW_BALL_DISTANCE {
+            Some(QuickThrow { team, x: self.state.x, y: self.state.y, out_at: self.time, touched_by })
+        } else {
+            None
+        };
+
+        // The ball is dead, it must not drag the line out mark along
+        self.ball_throw.active = false;
     }
 }
\ No newline at end of file
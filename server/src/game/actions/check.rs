@@ -1,6 +1,5 @@
 use crate::game::game_state::GameState;
 use crate::game::constants::*;
-use crate::game::models::*;
 
 impl GameState {
 
@@ -9,6 +8,7 @@ impl GameState {
         let distance = ((self.ball.x - self.state.x).powi(2) + (self.ball.y - self.state.y).powi(2)).sqrt();
         if !self.ball.is_carried && distance >= SCRUM_SIZE {
             self.state.name = "play".to_string();
+            self.scrum = None;
             self.state.x = self.ball.x;
             self.state.y = self.ball.y;
             print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
@@ -17,79 +17,27 @@ impl GameState {
     }
 
     //RUCK
-    pub fn check_tackler(&mut self) -> bool {
+    /// Team fouled by a tackler who did not roll away from the ruck.
+    pub fn check_tackler(&mut self) -> Option<char> {
+        let mut offender = None;
         for (team, player) in
         self.home_team.players.iter_mut().map(|p| ('H', p))
         .chain(self.away_team.players.iter_mut().map(|p| ('A', p))) {
             if player.is_tackler {
                 let distance = ((player.x - self.state.x).powi(2) + (player.y - self.state.y).powi(2)).sqrt();
+                player.is_tackler = false;
                 if distance < 1.0 {
-                    let team_fouled = if team == 'H' { 'A' } else { 'H' };
-                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                    print!("Tackler penalty: ball for {}\n", team_fouled);
-                    return true;
-                } else {
-                    player.is_tackler = false;
+                    offender = Some((team, player.number));
+                    break;
                 }
             }
         }
-        false
-    }
-
-    pub fn check_scrap(&mut self) {
-        let mut scrap_h_pound = 0.0;
-        let mut scrap_a_pound = 0.0;
-        let mut contest_player: Option<&mut Player> = None;
-        let mut contest_dist: f32 = f32::MAX;
-
-        for (team, player) in
-        self.home_team.players.iter_mut().map(|p| ('H', p))
-        .chain(self.away_team.players.iter_mut().map(|p| ('A', p))) {
-            let distance = ((player.x - self.state.x).powi(2) + (player.y - self.state.y).powi(2)).sqrt();
-            if distance < self.state.size && !player.is_tackle {
-                if team == 'H' {
-                    scrap_h_pound += player.pound;
-                    if self.state.team == 'A' && distance < contest_dist {
-                        contest_dist = distance;
-                        contest_player = Some(player);
-                    }
-                } else {
-                    scrap_a_pound += player.pound;
-                    if self.state.team == 'H' && distance < contest_dist {
-                        contest_dist = distance;
-                        contest_player = Some(player);
-                    }
-                }
-            }
-        }
-
-        let scrap_in = if self.state.team == 'H' { scrap_h_pound } else { scrap_a_pound };
-        let scrap_front = if self.state.team == 'H' { scrap_a_pound } else { scrap_h_pound };
-
-        if scrap_front > scrap_in {
-            if let Some(player) = contest_player {
-                //update ball
-                player.ball_pos = true;
-                self.ball.is_carried = true;
-                self.ball.z = 1.0;
-                if self.state.team == 'H' {
-                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                    print!("Away player {} picked up the ball from ruck\n", player.number);
-                    self.ball.x = player.x + if self.field.home_direction_try == 'N' { -0.5 } else { 0.5 };
-                } else {
-                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                    print!("Home player {} picked up the ball from ruck\n", player.number);
-                    self.ball.x = player.x + if self.field.home_direction_try == 'N' { 0.5 } else { -0.5 };
-                }
-                self.ball.y = player.y;
-
-                //update state
-                self.state.name = "play".to_string();
-                self.state.team = if self.state.team == 'H' { 'A' } else { 'H' };
-                self.state.x = self.ball.x;
-                self.state.y = self.ball.y;
-            }
-        }
+        let (team, number) = offender?;
+        let team_fouled = if team == 'H' { 'A' } else { 'H' };
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        print!("Tackler penalty: ball for {}\n", team_fouled);
+        self.record_infringement(team, number);
+        Some(team_fouled)
     }
 
     pub fn check_ball_position(&mut self) {
@@ -102,20 +50,22 @@ impl GameState {
         // Check if the ball is out of bounds
         if self.ball.y < 1.0 ||
         self.ball.y > self.field.height as f32 + 1.0 {
-            if self.ball.x > self.field.try_size as f32 + 1.0 ||
+            if self.ball.x > self.field.try_size as f32 + 1.0 &&
             self.ball.x < self.field.width as f32 + self.field.try_size as f32 + 1.0 {
                 // Check if the ball is out of bounds on the left or right side
                 print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
                 print!("Ball is out at {} {}\n", self.ball.x, self.ball.y);
-                if self.state.name == "free-kick" {
+                // Only a penalty kicked to touch keeps the throw
+                if self.state.name == "touch-kick" {
                     self.ask_line_out(self.state.team);
                 } else {
-                    self.ask_line_out(if self.state.team == 'H' {'A'} else {'B'});
+                    self.ask_line_out(if self.state.team == 'H' {'A'} else {'H'});
                 }
             } else {
                 // Check if the ball is out of bounds on the try zone (side)
                 print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                print!("Ball is out of try at {} {}\n", self.ball.x, self.ball.y)
+                print!("Ball is out of try at {} {}\n", self.ball.x, self.ball.y);
+                self.ball_dead_in_goal();
             }
         } else {
             // Check if the ball is out of bounds on the try zone (end)
@@ -124,7 +74,8 @@ impl GameState {
             (self.field.try_size as f32 * 2.0) +
             1.0 {
                 print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                print!("Ball is out of try at {} {}\n", self.ball.x, self.ball.y)
+                print!("Ball is out of try at {} {}\n", self.ball.x, self.ball.y);
+                self.ball_dead_in_goal();
             }
         }
 
@@ -0,0 +1,118 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::*;
+use crate::game::state::dispatch::{ACTION_HANDLERS, COMMAND_HANDLERS};
+
+impl GameState {
+    /// Game time at which the siren of the current period goes.
+    pub fn period_end(&self) -> u64 {
+        let half = self.field.half_duration * TIME_PER_MINUTE;
+        match self.phase {
+            Phase::FirstHalf => half,
+            Phase::SecondHalf => 2 * half,
+            Phase::ExtraTime => 2 * half + self.field.extra_duration * TIME_PER_MINUTE,
+            Phase::HalfTime | Phase::FullTime => self.time,
+        }
+    }
+
+    /// Whether the ball is dead in the current state: every `set-` state, the kick-offs, drop-outs and conversions,
+    /// and a scrum until the ball is put in.
+    pub fn is_ball_dead(&self) -> bool {
+        let name = self.state.name.as_str();
+        if name == "scrum" {
+            return self.scrum.is_some_and(|scrum| scrum.step != ScrumStep::Fed);
+        }
+        COMMAND_HANDLERS.iter().any(|(state, _)| *state == name)
+            || ACTION_HANDLERS.iter().any(|(state, _, dead)| *state == name && *dead)
+    }
+
+    // A conversion and a penalty or free kick awarded after the siren are still taken
+    fn is_kick_to_take(&self) -> bool {
+        matches!(self.state.name.as_str(), "set-transformation" | "transformation-kick" | "set-penalty" | "set-free-kick")
+    }
+
+    pub fn update_clock(&mut self) {
+        if self.phase == Phase::HalfTime || self.phase == Phase::FullTime {
+            return;
+        }
+        if !self.siren && self.time >= self.period_end() {
+            self.siren = true;
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Siren, play goes on until the ball is dead");
+        }
+        // After the siren the period only ends once the ball is dead
+        if self.siren && self.is_ball_dead() && !self.is_kick_to_take() {
+            self.end_period();
+        }
+    }
+
+    fn end_period(&mut self) {
+        let draw = self.home_team.score == self.away_team.score;
+        self.siren = false;
+        self.phase = match self.phase {
+            Phase::FirstHalf => Phase::HalfTime,
+            Phase::SecondHalf if draw && self.field.extra_duration > 0 => Phase::HalfTime,
+            _ => Phase::FullTime,
+        };
+
+        self.ball.is_carried = false;
+        self.ball_throw.active = false;
+        for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+            player.ball_pos = false;
+        }
+
+        if self.phase == Phase::FullTime {
+            self.state.name = "end".to_string();
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Full time: H {} - {} A", self.home_team.score, self.away_team.score);
+        } else {
+            self.state.name = "half-time".to_string();
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Half time: H {} - {} A", self.home_team.score, self.away_team.score);
+        }
+    }
+
+    /// Leave half-time: teams switch sides and the next period kicks off.
+    pub fn start_period(&mut self) {
+        let half = self.field.half_duration * TIME_PER_MINUTE;
+        let receive_team = if self.kick_off_team == 'H' { 'A' } else { 'H' };
+
+        let kick_team = if self.time < 2 * half {
+            self.phase = Phase::SecondHalf;
+            self.time = half;
+            receive_team
+        } else {
+            self.phase = Phase::ExtraTime;
+            self.time = 2 * half;
+            self.kick_off_team
+        };
+        self.field.home_direction_try = if self.field.home_direction_try == 'N' { 'S' } else { 'N' };
+
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Teams switch sides, home now attacks {}", self.field.home_direction_try);
+        self.setup_restart(kick_team);
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::game::game_state::GameState;
+    use crate::game::models::Phase;
+
+    #[test]
+    fn period_ends_on_the_first_dead_ball_after_the_siren() {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        game.time = game.period_end();
+        game.update_clock();
+        assert!(game.siren);
+        assert_eq!(game.phase, Phase::FirstHalf);
+
+        // A penalty awarded after the siren is still taken
+        game.state.name = "set-penalty".to_string();
+        game.update_clock();
+        assert_eq!(game.phase, Phase::FirstHalf);
+
+        game.state.name = "set-line_out".to_string();
+        game.update_clock();
+        assert_eq!(game.phase, Phase::HalfTime);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::Player;
+
+impl GameState {
+    /// Count a penalty offence by player `number` of `team`.
+    /// Repeated offences by the player, or by the team as a whole, are sanctioned with a card.
+    /// An offence repeated while the opponents already play advantage is not counted again.
+    pub fn record_infringement(&mut self, team: char, number: usize) {
+        if self.advantage.is_some_and(|a| a.team != team) {
+            return;
+        }
+        let squad = if team == 'H' { &mut self.home_team } else { &mut self.away_team };
+        let Some(index) = squad.players.iter().position(|p| p.number == number) else {
+            return;
+        };
+        squad.penalties_conceded += 1;
+        squad.players[index].infringements += 1;
+        let repeated = squad.players[index].infringements >= PLAYER_INFRINGEMENTS_CARD;
+        let team_warning = squad.penalties_conceded % TEAM_INFRINGEMENTS_CARD == 0;
+        if !repeated && !team_warning {
+            return;
+        }
+
+        let mut player = self.leave_field(team, index);
+        player.infringements = 0;
+        player.yellow_cards += 1;
+
+        let squad = if team == 'H' { &mut self.home_team } else { &mut self.away_team };
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        if player.yellow_cards >= 2 {
+            println!("Red card for player {} {}, second yellow", team, number);
+            squad.sent_off.push(player);
+        } else {
+            let until = self.time + SIN_BIN_TIME;
+            let reason = if repeated { "repeated infringements" } else { "team infringements" };
+            println!("Yellow card for player {} {} ({}), back at {:.2}", team, number, reason, (until as f32)/100.0);
+            squad.sin_bin.push((player, until));
+        }
+    }
+
+    /// Count a scrum or a maul brought down by player `number` of `team`, a penalty offence.
+    /// A player who keeps collapsing them is shown a direct red card.
+    pub fn record_collapse(&mut self, team: char, number: usize) {
+        if self.advantage.is_some_and(|a| a.team != team) {
+            return;
+        }
+        let squad = if team == 'H' { &mut self.home_team } else { &mut self.away_team };
+        let Some(index) = squad.players.iter().position(|p| p.number == number) else {
+            return;
+        };
+        squad.players[index].collapses += 1;
+        if squad.players[index].collapses < COLLAPSES_RED_CARD {
+            self.record_infringement(team, number);
+            return;
+        }
+
+        squad.penalties_conceded += 1;
+        let player = self.leave_field(team, index);
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Red card for player {} {}, repeated collapses", team, number);
+        let squad = if team == 'H' { &mut self.home_team } else { &mut self.away_team };
+        squad.sent_off.push(player);
+    }
+
+    // Take a carded player off the field, dropping the ball if they carried it
+    fn leave_field(&mut self, team: char, index: usize) -> Player {
+        let squad = if team == 'H' { &mut self.home_team } else { &mut self.away_team };
+        let mut player = squad.players.remove(index);
+        if player.ball_pos {
+            self.ball.is_carried = false;
+        }
+        player.ball_pos = false;
+        player.is_tackle = false;
+        player.is_tackler = false;
+        player
+    }
+
+    /// Sin-binned players come back on the touch line at halfway once their ten minutes are served.
+    pub fn update_sin_bin(&mut self) {
+        let halfway = self.field.try_size as f32 + 1.0 + self.field.width as f32 / 2.0;
+        for team in ['H', 'A'] {
+            let squad = if team == 'H' { &mut self.home_team } else { &mut self.away_team };
+            while let Some(index) = squad.sin_bin.iter().position(|(_, until)| *until <= self.time) {
+                let (mut player, _) = squad.sin_bin.remove(index);
+                player.x = halfway;
+                player.y = 1.0;
+                player.is_offside = false;
+                print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                println!("Player {} {} comes back from the sin bin", team, player.number);
+                squad.players.push(player);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::game_state::GameState;
+    use crate::game::constants::*;
+    use crate::game::models::Sanction;
+
+    #[test]
+    fn carded_front_rower_leaves_the_rest_of_the_pack_in_place() {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        for _ in 0..PLAYER_INFRINGEMENTS_CARD {
+            game.record_infringement('H', 3);
+        }
+        assert!(game.home_team.sin_bin.iter().any(|(p, _)| p.number == 3));
+
+        // The other forwards still push and the number 8 still runs, the backs stay out
+        for number in [1, 2, 4, 5, 6, 7, 8] {
+            assert!(game.scrum_pound('H', number).is_some(), "player {} pushes", number);
+        }
+        for number in 9..=15 {
+            assert!(game.scrum_pound('H', number).is_none(), "player {} does not push", number);
+        }
+        assert_eq!(game.scrum_position('H', 8), 8);
+
+        // Back from the sin bin at the end of the squad, the prop pushes again
+        game.time += SIN_BIN_TIME;
+        game.update_sin_bin();
+        assert_eq!(game.home_team.players.last().map(|p| p.number), Some(3));
+        assert!(game.scrum_pound('H', 3).is_some());
+    }
+
+    #[test]
+    fn second_yellow_card_is_a_red_card() {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        for _ in 0..PLAYER_INFRINGEMENTS_CARD {
+            game.record_infringement('A', 7);
+        }
+        game.time += SIN_BIN_TIME;
+        game.update_sin_bin();
+        assert!(game.away_team.players.iter().any(|p| p.number == 7));
+
+        for _ in 0..PLAYER_INFRINGEMENTS_CARD {
+            game.record_infringement('A', 7);
+        }
+        assert!(game.away_team.sent_off.iter().any(|p| p.number == 7));
+        assert!(game.away_team.sin_bin.is_empty());
+    }
+
+    #[test]
+    fn offences_during_the_opponents_advantage_count_once() {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        game.record_infringement('A', 7);
+        assert!(!game.play_advantage('H', 60.0, 30.0, Sanction::Penalty));
+        for _ in 0..PLAYER_INFRINGEMENTS_CARD {
+            game.record_infringement('A', 7);
+        }
+        let player = game.away_team.players.iter().find(|p| p.number == 7).unwrap();
+        assert_eq!(player.infringements, 1);
+    }
+
+    #[test]
+    fn third_collapse_is_a_direct_red_card() {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        for _ in 0..COLLAPSES_RED_CARD {
+            game.record_collapse('A', 1);
+        }
+        assert!(game.away_team.sent_off.iter().any(|p| p.number == 1));
+        assert!(game.snapshot().cards.iter().any(|c| c.team == 'A' && c.number == 1 && c.kind == 'R'));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::game::game_state::GameState;
+
+impl GameState {
+    /// x of the goal line `team` defends.
+    pub fn goal_line(&self, team: char) -> f32 {
+        if self.attack_sign(team) > 0.0 {
+            self.field.try_size as f32 + 1.0
+        } else {
+            self.field.width as f32 + self.field.try_size as f32 + 1.0
+        }
+    }
+
+    /// x of the 22 m line in front of the goal line `team` defends.
+    pub fn line_22(&self, team: char) -> f32 {
+        self.goal_line(team) + self.attack_sign(team) * self.field.line_22 as f32
+    }
+
+    /// Team that played the ball last: the carrier, or whoever kicked, passed or dropped it.
+    pub fn last_touch(&self) -> char {
+        if self.home_team.players.iter().any(|p| p.ball_pos) {
+            'H'
+        } else if self.away_team.players.iter().any(|p| p.ball_pos) {
+            'A'
+        } else {
+            self.ball_throw.team
+        }
+    }
+
+    /// Ball dead in-goal: a 22 m drop-out when the attackers put it there,
+    /// a goal-line drop-out when the defenders did, a kick-off after a missed conversion.
+    pub fn ball_dead_in_goal(&mut self) {
+        if self.state.name == "transformation-kick" {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Transformation missed by team {}", self.state.team);
+            self.setup_restart(if self.state.team == 'H' {'A'} else {'H'});
+            return;
+        }
+        let south = self.ball.x < self.field.width as f32 / 2.0 + self.field.try_size as f32 + 1.0;
+        let defending = if (self.attack_sign('H') > 0.0) == south { 'H' } else { 'A' };
+        let last_touch = self.last_touch();
+
+        for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+            player.ball_pos = false;
+        }
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        if last_touch == defending {
+            println!("Ball made dead in-goal by team {}, goal-line drop-out", defending);
+            self.setup_drop_out(defending, self.goal_line(defending));
+        } else {
+            println!("Ball dead in-goal from team {}, 22 m drop-out", last_touch);
+            self.setup_drop_out(defending, self.line_22(defending));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::game_state::GameState;
+
+    // Loose ball last played by `team` dead behind the away goal line, home attacks towards it
+    fn dead_in_away_in_goal(team: char) -> GameState {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        for player in game.home_team.players.iter_mut().chain(game.away_team.players.iter_mut()) {
+            player.ball_pos = false;
+        }
+        game.ball.is_carried = false;
+        game.ball.x = game.goal_line('A') + 3.0;
+        game.ball_throw.team = team;
+        game.ball_dead_in_goal();
+        game
+    }
+
+    #[test]
+    fn attackers_putting_the_ball_dead_give_a_22_drop_out() {
+        let game = dead_in_away_in_goal('H');
+        assert_eq!(game.state.name, "drop-out");
+        assert_eq!(game.state.team, 'A');
+        assert_eq!(game.state.x, game.line_22('A'));
+    }
+
+    #[test]
+    fn defenders_putting_the_ball_dead_give_a_goal_line_drop_out() {
+        let game = dead_in_away_in_goal('A');
+        assert_eq!(game.state.name, "drop-out");
+        assert_eq!(game.state.team, 'A');
+        assert_eq!(game.state.x, game.goal_line('A'));
+    }
+}
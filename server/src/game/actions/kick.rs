@@ -1,3 +1,5 @@
+use rand::Rng;
+
 use crate::game::game_state::GameState;
 use crate::game::constants::*;
 use crate::game::models::*;
@@ -17,22 +19,23 @@ impl GameState {
         if let Some(p) = players.iter_mut().find(|p| p.number == number as usize) {
             if p.ball_pos {
                 p.ball_pos = false;
+                let kicker_x = p.x;
                 self.ball.is_carried = false;
                 self.ball.z = 0.0;
 
                 let mut speed = (p.foot * GRAVITY).sqrt() / 1.12; // Empirical correction
-                let is_successful = rand::random::<f32>() * 100.0 < p.p_foot;
+                let is_successful = self.rng.random::<f32>() * 100.0 < p.p_foot;
 
                 if !is_successful {
                     print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
                     print!("Failed kick\n");
-                    let horizontal_dev_deg = (rand::random::<f32>() * 20.0) - 10.0; // -10° to +10°
+                    let horizontal_dev_deg = (self.rng.random::<f32>() * 20.0) - 10.0; // -10° to +10°
                     dir += horizontal_dev_deg.to_radians();
 
-                    let vertical_dev_deg = (rand::random::<f32>() * 10.0) - 5.0; // -5° to +5°
+                    let vertical_dev_deg = (self.rng.random::<f32>() * 10.0) - 5.0; // -5° to +5°
                     elev += vertical_dev_deg.to_radians();
 
-                    let loss = 0.80 + rand::random::<f32>() * 0.2; // between 80% and 100%
+                    let loss = 0.80 + self.rng.random::<f32>() * 0.2; // between 80% and 100%
                     speed *= loss;
                 } else {
                     print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
@@ -50,8 +53,19 @@ impl GameState {
                     vx,
                     vy,
                     vz,
+                    exposure: KICK_EXPOSURE,
+                    // Low kicks turn end over end, high kicks are struck as spirals
+                    spin: KICK_SPIN * elev.cos().powi(2),
+                    bounces: 0,
+                    rolling: false,
+                    kind: ThrowKind::Kick,
+                    team,
+                    origin_x: self.ball.x,
+                    origin_y: self.ball.y,
                     active: true,
                 };
+                self.flag_kick_chase(team, kicker_x);
+                self.clear_offside(if team == 'H' { 'A' } else { 'H' });
             }
         }
     }
@@ -0,0 +1,84 @@
+use rand::Rng;
+
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::*;
+
+impl GameState {
+    /// The ball escapes the hands of a player, it is a knock-on when it was in the air
+    /// and goes towards the opponents' dead-ball line. A failed pick-up off the ground is not.
+    pub fn fumble(&mut self, team: char, number: usize, x: f32, y: f32, in_flight: bool) {
+        // The ball carries on the way it came, spread around it, a ball almost still goes off the way the player faces
+        let (in_vx, in_vy) = (self.ball_throw.vx, self.ball_throw.vy);
+        let incoming = if in_vx.hypot(in_vy) > KNOCK_SPEED {
+            in_vy.atan2(in_vx)
+        } else if self.attack_sign(team) > 0.0 {
+            0.0
+        } else {
+            std::f32::consts::PI
+        };
+        let spread = (self.rng.random::<f32>() + self.rng.random::<f32>() - 1.0) * KNOCK_SPREAD.to_radians();
+        let direction = incoming + spread;
+        let vx = KNOCK_SPEED * direction.cos();
+        let vy = KNOCK_SPEED * direction.sin();
+
+        self.ball.is_carried = false;
+        self.ball_throw = BallThrow {
+            prev_x: self.ball.x,
+            prev_y: self.ball.y,
+            prev_z: self.ball.z,
+            vx,
+            vy,
+            vz: 1.0,
+            exposure: PASS_EXPOSURE,
+            spin: 0.0,
+            bounces: 0,
+            rolling: false,
+            kind: ThrowKind::Loose,
+            team,
+            origin_x: x,
+            origin_y: y,
+            active: true,
+        };
+
+        let forward = vx * self.attack_sign(team) > 0.0;
+        if !in_flight || !forward {
+            return;
+        }
+
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Knock-on by player {} {}", team, number);
+        self.play_advantage(if team == 'H' { 'A' } else { 'H' }, x, y, Sanction::Scrum);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::game_state::GameState;
+    use crate::game::models::Sanction;
+
+    // Home attacks towards increasing x, player H 12 fumbles a ball coming at `vx` m/s
+    fn fumbles(vx: f32) -> (usize, usize) {
+        let mut game = GameState::seeded(7, "play H 60 30");
+        let (mut forward, mut knock_ons) = (0, 0);
+        for _ in 0..50 {
+            game.advantage = None;
+            game.ball_throw.vx = vx;
+            game.ball_throw.vy = 0.0;
+            game.fumble('H', 12, 60.0, 30.0, true);
+            if game.ball_throw.vx > 0.0 {
+                forward += 1;
+            }
+            if game.advantage.is_some_and(|a| a.team == 'A' && a.sanction == Sanction::Scrum) {
+                knock_ons += 1;
+            }
+        }
+        (forward, knock_ons)
+    }
+
+    #[test]
+    fn fumbled_ball_goes_on_the_way_it_came() {
+        assert_eq!(fumbles(-10.0), (0, 0));
+        assert_eq!(fumbles(10.0), (50, 50));
+    }
+}
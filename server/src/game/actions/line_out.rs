@@ -1,15 +1,211 @@
+use rand::Rng;
+
 use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::*;
 
 impl GameState {
     pub fn throw_from_line_out(&mut self, team: char, number: i32, angle: f32) {
-        println!("{} {} {}", team, number, angle);
+        if team != self.state.team || self.ball_throw.active {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Player {} {} cannot throw in this line out", team, number);
+            return;
+        }
+        let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+        let Some(p) = players.iter_mut().find(|p| p.number == number as usize && p.ball_pos) else {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Player {} {} does not have the ball to throw", team, number);
+            return;
+        };
+        p.ball_pos = false;
+        self.ball.is_carried = false;
+
+        // The throw has to go in straight, at a right angle to the touch line
+        let straight = if self.state.y == 0.5 { 90.0 } else { 270.0 };
+        let deviation = (angle - straight + 180.0).rem_euclid(360.0) - 180.0;
+        if deviation.abs() > LINE_OUT_STRAIGHT_TOLERANCE {
+            let opponent = if team == 'H' { 'A' } else { 'H' };
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Throw not straight by player {} {}, option for team {}", team, number, opponent);
+            self.state.name = "set-not-straight".to_string();
+            self.state.team = opponent;
+            return;
+        }
+
+        self.launch_throw_in(team, angle);
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Player {} {} throws in the line out", team, number);
+    }
+
+    /// Throw the ball in from touch, shared by the line out and the quick throw-in.
+    pub fn launch_throw_in(&mut self, team: char, angle: f32) {
+        let direction = angle.to_radians();
+        let elevation = LINE_OUT_THROW_ELEVATION.to_radians();
+        self.ball.z = LINE_OUT_RELEASE_HEIGHT;
+        self.ball_throw = BallThrow {
+            prev_x: self.ball.x,
+            prev_y: self.ball.y,
+            prev_z: self.ball.z,
+            vx: LINE_OUT_THROW_SPEED * elevation.cos() * direction.cos(),
+            vy: LINE_OUT_THROW_SPEED * elevation.cos() * direction.sin(),
+            vz: LINE_OUT_THROW_SPEED * elevation.sin(),
+            exposure: PASS_EXPOSURE,
+            spin: 0.0,
+            bounces: 0,
+            rolling: false,
+            kind: ThrowKind::LineOut,
+            team,
+            origin_x: self.ball.x,
+            origin_y: self.ball.y,
+            active: true,
+        };
     }
 
     pub fn jump_line_out(&mut self, team: char, number: i32) {
-        println!("{} {}", team, number);
+        let time = self.time;
+        let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+        if let Some(p) = players.iter_mut().find(|p| p.number == number as usize) {
+            if p.jump_until < time {
+                p.jump_height = JUMP_HEIGHT;
+            }
+            p.jump_until = time + JUMP_TIME;
+        }
     }
 
+    /// A lifter holds up the closest teammate in the air, two lifters at most count.
     pub fn lift_line_out(&mut self, team: char, number: i32) {
-        println!("{} {}", team, number);
+        let time = self.time;
+        let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+        let Some(lifter) = players.iter().find(|p| p.number == number as usize).copied() else {
+            return;
+        };
+        let jumper = players.iter_mut()
+            .filter(|p| p.number != lifter.number && p.jump_until >= time)
+            .map(|p| (((p.x - lifter.x).powi(2) + (p.y - lifter.y).powi(2)).sqrt(), p))
+            .filter(|(distance, _)| *distance < LIFT_DISTANCE)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, p)| p);
+
+        if let Some(jumper) = jumper {
+            jumper.jump_height = (jumper.jump_height + LIFT_HEIGHT).min(JUMP_HEIGHT + 2.0 * LIFT_HEIGHT);
+            jumper.jump_until = time + LIFT_TIME;
+        }
+    }
+
+    /// Jumpers under the ball compete for it, the highest reach wins, reach grows with `Player::size`.
+    /// The winner's side plays on, or mauls when teammates and opponents are bound on the catcher.
+    pub fn contest_line_out(&mut self) {
+        let (bx, by, bz, time) = (self.ball.x, self.ball.y, self.ball.z, self.time);
+        let mut winner: Option<(char, usize, f32)> = None;
+        for (team, player) in self.home_team.players.iter().map(|p| ('H', p))
+            .chain(self.away_team.players.iter().map(|p| ('A', p))) {
+            if player.jump_until < time {
+                continue;
+            }
+            let distance = ((player.x - bx).powi(2) + (player.y - by).powi(2)).sqrt();
+            let reach = player.size / 100.0 * REACH_RATIO + player.jump_height;
+            if distance > LINE_OUT_CATCH_DISTANCE || reach < bz {
+                continue;
+            }
+            let score = reach + self.rng.random::<f32>() * LINE_OUT_CONTEST_LUCK;
+            if winner.is_none_or(|(_, _, best)| score > best) {
+                winner = Some((team, player.number, score));
+            }
+        }
+
+        if let Some((team, number, _)) = winner {
+            let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+            let Some(p) = players.iter_mut().find(|p| p.number == number) else {
+                return;
+            };
+            p.ball_pos = true;
+            let (x, y) = (p.x, p.y);
+            self.ball = Ball { x, y, z: 1.0, is_carried: true };
+            self.ball_throw.active = false;
+            self.end_jumps();
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Line out won by player {} {}", team, number);
+
+            let near = |o: &Player| ((o.x - x).powi(2) + (o.y - y).powi(2)).sqrt() < MAUL_BIND;
+            let (own, opponents) = if team == 'H' {
+                (&self.home_team.players, &self.away_team.players)
+            } else {
+                (&self.away_team.players, &self.home_team.players)
+            };
+            if own.iter().any(|o| o.number != number && near(o)) && opponents.iter().any(near) {
+                self.setup_maul(x, y, team);
+            } else {
+                self.state.name = "play".to_string();
+                self.state.team = team;
+                self.state.x = x;
+                self.state.y = y;
+            }
+        } else if !self.ball_throw.active || self.ball.z <= 0.0 {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Nobody caught the line out throw, resuming play");
+            self.end_jumps();
+            self.state.name = "play".to_string();
+            self.state.x = self.ball.x;
+            self.state.y = self.ball.y;
+        }
+    }
+
+    fn end_jumps(&mut self) {
+        for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+            player.jump_height = 0.0;
+            player.jump_until = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::{Action, ClientMessage, Command, PlayerAction};
+
+    use crate::game::game_state::GameState;
+
+    const LINE: [usize; 5] = [1, 4, 6, 7, 5];
+
+    // Home line out on the home side touch line at x = 60
+    fn line_out() -> GameState {
+        let mut game = GameState::seeded(5, "play H 60 30");
+        game.state.name = "set-line_out".to_string();
+        game.state.team = 'H';
+        game.state.x = 60.0;
+        game.state.y = 0.5;
+        let command = Command::LineOut { number: LINE.len(), home_line: LINE.to_vec(), away_line: LINE.to_vec() };
+        game.dispatch(ClientMessage::Command { state: "set-line_out".to_string(), command });
+        game
+    }
+
+    fn tick(game: &mut GameState, actions: Vec<PlayerAction>) {
+        game.dispatch(ClientMessage::Actions { state: game.state.name.clone(), actions });
+    }
+
+    #[test]
+    fn crooked_throw_gives_the_option_to_the_opponents() {
+        let mut game = line_out();
+        tick(&mut game, vec![PlayerAction { team: 'H', number: 2, action: Action::Throw { angle: 60.0 } }]);
+        assert_eq!(game.state.name, "set-not-straight");
+        assert_eq!(game.state.team, 'A');
+    }
+
+    #[test]
+    fn lifted_jumpers_win_the_throw_against_a_standing_line() {
+        let mut game = line_out();
+        tick(&mut game, vec![PlayerAction { team: 'H', number: 2, action: Action::Throw { angle: 90.0 } }]);
+        for _ in 0..10 {
+            if game.state.name != "line_out" {
+                break;
+            }
+            let mut actions: Vec<PlayerAction> = [4, 5, 6, 7].iter()
+                .map(|&number| PlayerAction { team: 'H', number, action: Action::Jump })
+                .collect();
+            actions.push(PlayerAction { team: 'H', number: 1, action: Action::Lift });
+            tick(&mut game, actions);
+        }
+        assert!(game.state.name == "play" || game.state.name == "maul", "state {}", game.state.name);
+        assert_eq!(game.state.team, 'H');
+        assert!(game.home_team.players.iter().any(|p| p.ball_pos));
     }
-}
\ No newline at end of file
+}
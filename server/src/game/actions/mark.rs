@@ -0,0 +1,102 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::ThrowKind;
+
+impl GameState {
+    /// Team whose kick is still on the full, None once it bounced.
+    pub fn kick_on_the_full(&self) -> Option<char> {
+        if self.ball_throw.active && self.ball_throw.kind == ThrowKind::Kick &&
+        self.ball_throw.bounces == 0 && !self.ball_throw.rolling {
+            Some(self.ball_throw.team)
+        } else {
+            None
+        }
+    }
+
+    /// Remember a clean catch of the opponents' kick, the catcher may call a mark on it.
+    pub fn record_fair_catch(&mut self, kicker: Option<char>) {
+        let Some(kicker) = kicker else {
+            return;
+        };
+        if !self.ball.is_carried || self.state.name != "play" || self.state.team == kicker {
+            return;
+        }
+        let players = if self.state.team == 'H' { &self.home_team.players } else { &self.away_team.players };
+        if let Some(p) = players.iter().find(|p| p.ball_pos) {
+            self.fair_catch = Some((self.state.team, p.number, self.time));
+        }
+    }
+
+    /// Mark called by a defender who caught the opponents' kick cleanly inside their own 22.
+    /// Play stops and the catcher's team gets a free kick where the mark was called.
+    pub fn call_mark(&mut self, team: char, number: usize) -> bool {
+        let Some((catch_team, catcher, time)) = self.fair_catch else {
+            return false;
+        };
+        if catch_team != team || catcher != number || self.time > time + MARK_TIME || self.state.name != "play" {
+            return false;
+        }
+        self.fair_catch = None;
+        let line_22 = self.line_22(team);
+        let sign = self.attack_sign(team);
+        let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+        let Some(p) = players.iter_mut().find(|p| p.number == number && p.ball_pos) else {
+            return false;
+        };
+        if (p.x - line_22) * sign > 0.0 {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Player {} {} calls a mark outside their 22, play on", team, number);
+            return false;
+        }
+        let (x, y) = (p.x, p.y);
+        p.ball_pos = false;
+
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Mark by player {} {} at {} {}", team, number, x, y);
+        self.setup_free_kick_choice(team, x, y);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::{Action, ClientMessage, PlayerAction};
+
+    use crate::game::game_state::GameState;
+
+    // Away full back caught a home kick cleanly at `x` and calls a mark
+    fn mark_at(x: f32) -> GameState {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        for player in game.home_team.players.iter_mut().chain(game.away_team.players.iter_mut()) {
+            player.ball_pos = false;
+        }
+        if let Some(p) = game.away_team.players.iter_mut().find(|p| p.number == 15) {
+            p.x = x;
+            p.y = 30.0;
+            p.ball_pos = true;
+        }
+        game.ball.is_carried = true;
+        game.ball.x = x;
+        game.ball.y = 30.0;
+        game.state.team = 'A';
+        game.fair_catch = Some(('A', 15, game.time));
+        let actions = vec![PlayerAction { team: 'A', number: 15, action: Action::Mark }];
+        game.dispatch(ClientMessage::Actions { state: "play".to_string(), actions });
+        game
+    }
+
+    #[test]
+    fn mark_inside_the_22_gives_a_free_kick() {
+        // The away 22 m line is at x = 89, the goal line at 111
+        let game = mark_at(95.0);
+        assert_eq!(game.state.name, "set-free-kick");
+        assert_eq!(game.state.team, 'A');
+    }
+
+    #[test]
+    fn mark_outside_the_22_is_play_on() {
+        let game = mark_at(70.0);
+        assert_eq!(game.state.name, "play");
+        assert!(game.fair_catch.is_none());
+    }
+}
@@ -0,0 +1,99 @@
+use rand::Rng;
+
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+
+impl GameState {
+    /// Weight a player puts on the maul, None when not bound to it.
+    pub fn maul_pound(&self, team: char, number: i32) -> Option<f32> {
+        let players = if team == 'H' { &self.home_team.players } else { &self.away_team.players };
+        players.iter()
+            .find(|p| p.number == number as usize)
+            .filter(|p| ((p.x - self.state.x).powi(2) + (p.y - self.state.y).powi(2)).sqrt() < self.state.size)
+            .map(|p| p.pound)
+    }
+
+    /// Only the ball carrier can take the ball out of the maul.
+    pub fn release_maul(&mut self, team: char, number: i32) -> bool {
+        let players = if team == 'H' { &self.home_team.players } else { &self.away_team.players };
+        if !players.iter().any(|p| p.number == number as usize && p.ball_pos) {
+            return false;
+        }
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Ball out of maul, resuming play");
+        self.state.name = "play".to_string();
+        self.use_it_deadline = None;
+        true
+    }
+
+    pub fn update_maul(&mut self, maul_h_pound: f32, maul_a_pound: f32) {
+        let attack = self.state.team;
+        let defense = if attack == 'H' { 'A' } else { 'H' };
+        let (maul_in, maul_front) = if attack == 'H' { (maul_h_pound, maul_a_pound) } else { (maul_a_pound, maul_h_pound) };
+
+        // A maul needs a player of each team bound on the ball carrier
+        if maul_in == 0.0 || maul_front == 0.0 {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Maul broken up, resuming play");
+            self.state.name = "play".to_string();
+            self.use_it_deadline = None;
+            return;
+        }
+
+        // Defenders much heavier than the attackers may bring the maul down, the nearest one is penalised
+        if maul_front > maul_in * MAUL_COLLAPSE_RATIO && self.rng.random::<f32>() < MAUL_COLLAPSE_CHANCE {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Maul collapsed by team {}", defense);
+            if let Some(number) = self.maul_front_player(defense) {
+                self.record_collapse(defense, number);
+            }
+            // The maul is over, the attackers play on under advantage unless the referee stopped play
+            if !self.foul_play(attack, self.state.x, self.state.y) {
+                self.state.name = "play".to_string();
+            }
+            self.use_it_deadline = None;
+            return;
+        }
+
+        let advance = (maul_in - maul_front) / (maul_in + maul_front) * MAUL_SPEED;
+        if advance > MAUL_MIN_ADVANCE {
+            self.move_maul(advance * self.attack_sign(attack));
+        } else if self.use_it_deadline.is_none() {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Maul has stopped, use it");
+            self.use_it_deadline = Some(self.time + USE_IT_TIME);
+        }
+
+        if self.use_it_deadline.is_some_and(|deadline| self.time >= deadline) {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Ball not used, turnover scrum for team {}", defense);
+            self.use_it_deadline = None;
+            for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+                player.ball_pos = false;
+            }
+            self.setup_scrum(defense, self.state.x, self.state.y);
+        }
+    }
+
+    // Player of `team` bound on the maul closest to the ball
+    fn maul_front_player(&self, team: char) -> Option<usize> {
+        let players = if team == 'H' { &self.home_team.players } else { &self.away_team.players };
+        let distance = |x: f32, y: f32| ((x - self.state.x).powi(2) + (y - self.state.y).powi(2)).sqrt();
+        players.iter()
+            .filter(|p| distance(p.x, p.y) < self.state.size)
+            .min_by(|a, b| distance(a.x, a.y).total_cmp(&distance(b.x, b.y)))
+            .map(|p| p.number)
+    }
+
+    // Everybody bound on the maul moves with it, the ball carrier included
+    fn move_maul(&mut self, dx: f32) {
+        let (x, y, size) = (self.state.x, self.state.y, self.state.size);
+        for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+            if ((player.x - x).powi(2) + (player.y - y).powi(2)).sqrt() < size {
+                player.x += dx;
+            }
+        }
+        self.state.x += dx;
+        self.ball.x += dx;
+    }
+}
@@ -8,4 +8,17 @@ pub mod update_ball;
 pub mod check;
 pub mod setup;
 pub mod ask;
-pub mod line_out;
\ No newline at end of file
+pub mod line_out;
+pub mod clock;
+pub mod substitution;
+pub mod knock_on;
+pub mod offside;
+pub mod maul;
+pub mod quick_throw;
+pub mod drop_out;
+pub mod mark;
+pub mod advantage;
+pub mod discipline;
+pub mod penalty_try;
+pub mod scrum;
+pub mod ruck;
\ No newline at end of file
@@ -0,0 +1,125 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::*;
+
+impl GameState {
+    /// 1.0 when `team` attacks towards growing x, -1.0 otherwise.
+    pub fn attack_sign(&self, team: char) -> f32 {
+        if (team == 'H') == (self.field.home_direction_try == 'N') { 1.0 } else { -1.0 }
+    }
+
+    /// Offside line of `team` in the current phase, None in open play.
+    pub fn offside_line(&self, team: char) -> Option<f32> {
+        let sign = self.attack_sign(team);
+        match self.state.name.as_str() {
+            "ruck" | "maul" => Some(self.state.x - sign * self.state.size),
+            "scrum" => Some(self.state.x - sign * (SCRUM_SIZE + SCRUM_OFFSIDE)),
+            "line_out" => Some(self.state.x - sign * LINE_OUT_OFFSIDE),
+            _ => None,
+        }
+    }
+
+    // Players bound in the set piece are not held by its offside line
+    fn is_in_set_piece(&self, player: &Player) -> bool {
+        match self.state.name.as_str() {
+            "ruck" | "maul" => ((player.x - self.state.x).powi(2) + (player.y - self.state.y).powi(2)).sqrt() < self.state.size,
+            "scrum" => player.position < 10,
+            "line_out" => (player.x - self.state.x).abs() <= LINE_OUT_CORRIDOR || player.position == 2 || player.position == 9,
+            _ => false,
+        }
+    }
+
+    pub fn is_offside(&self, team: char, player: &Player) -> bool {
+        if self.is_in_set_piece(player) {
+            return false;
+        }
+        match self.offside_line(team) {
+            Some(line) => (player.x - line) * self.attack_sign(team) > 0.0,
+            None => player.is_offside,
+        }
+    }
+
+    /// A player takes part in play: when offside the opponents play advantage for a penalty.
+    /// A run `direction` back towards their own side is allowed, the player is retiring.
+    /// Returns true when the referee stopped play.
+    pub fn check_offside(&mut self, team: char, number: i32, direction: Option<f32>) -> bool {
+        let players = if team == 'H' { &self.home_team.players } else { &self.away_team.players };
+        let Some(player) = players.iter().find(|p| p.number == number as usize).copied() else {
+            return false;
+        };
+        if !self.is_offside(team, &player) {
+            return false;
+        }
+        if let Some(direction) = direction
+            && direction.to_radians().cos() * self.attack_sign(team) < 0.0 {
+            return false;
+        }
+
+        // Running on while the opponents already play advantage for a penalty is the same offence
+        let opponent = if team == 'H' { 'A' } else { 'H' };
+        if self.advantage.is_some_and(|a| a.team == opponent && a.sanction == Sanction::Penalty) {
+            return false;
+        }
+
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Player {} {} is offside", team, number);
+        let (x, y) = if self.offside_line(team).is_some() { (self.state.x, self.state.y) } else { (player.x, player.y) };
+        self.record_infringement(team, player.number);
+        self.play_advantage(opponent, x, y, Sanction::Penalty)
+    }
+
+    // KICK-CHASE
+
+    /// Teammates in front of the kicker are offside until put onside.
+    pub fn flag_kick_chase(&mut self, team: char, kicker_x: f32) {
+        let sign = self.attack_sign(team);
+        let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+        for player in players.iter_mut() {
+            player.is_offside = (player.x - kicker_x) * sign > 0.0;
+        }
+    }
+
+    /// The opponents played the ball, every player of `team` is onside again.
+    pub fn clear_offside(&mut self, team: char) {
+        let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+        for player in players.iter_mut() {
+            player.is_offside = false;
+        }
+    }
+
+    // Offside players are put onside once an onside teammate, the kicker included, runs past them
+    pub fn update_offside(&mut self) {
+        for team in ['H', 'A'] {
+            let sign = self.attack_sign(team);
+            let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+            let front = players.iter()
+                .filter(|p| !p.is_offside)
+                .map(|p| p.x * sign)
+                .fold(f32::MIN, f32::max);
+            for player in players.iter_mut().filter(|p| p.is_offside && p.x * sign <= front) {
+                player.is_offside = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::{Action, ClientMessage, PlayerAction};
+
+    use crate::game::game_state::GameState;
+    use crate::game::models::Sanction;
+
+    #[test]
+    fn offside_player_running_on_after_a_kick_is_penalised_once() {
+        let mut game = GameState::seeded(3, "play H 60 30");
+        game.flag_kick_chase('H', 0.0);
+        for _ in 0..2 {
+            let actions = vec![PlayerAction { team: 'H', number: 14, action: Action::Run { direction: 0.0 } }];
+            game.dispatch(ClientMessage::Actions { state: "play".to_string(), actions });
+        }
+        assert!(game.advantage.is_some_and(|a| a.team == 'A' && a.sanction == Sanction::Penalty));
+        let player = game.home_team.players.iter().find(|p| p.number == 14).unwrap();
+        assert_eq!(player.infringements, 1);
+    }
+}
@@ -3,12 +3,9 @@ use crate::game::constants::*;
 use crate::game::models::*;
 
 impl GameState {
-    pub fn pass(&mut self, team: char, number: i32, direction: f32) -> bool {
+    pub fn pass(&mut self, team: char, number: i32, direction: f32) {
         let direction_rad = direction.to_radians();
         let high_rad = (PASS_HEIGHT as f32).to_radians();
-        let mut pass_invalid = false;
-        let mut px = 0.0;
-        let mut py = 0.0;
 
         let players = if team == 'H' {
             &mut self.home_team.players
@@ -18,42 +15,54 @@ impl GameState {
 
         if let Some(p) = players.iter_mut().find(|p| p.number == number as usize) {
             if p.ball_pos {
-                px = p.x;
-                py = p.y;
-                let invalid_direction = (((team == 'H' &&  self.field.home_direction_try == 'N') ||
-                (team == 'A' &&  self.field.home_direction_try == 'S')) &&
-                (direction > 270.0 || direction < 90.0)) ||
-                (((team == 'H' &&  self.field.home_direction_try == 'S') ||
-                (team == 'A' &&  self.field.home_direction_try == 'N')) &&
-                direction > 90.0 && direction < 270.0);
-
-                if invalid_direction {
-                    pass_invalid = true;
-                } else {
-                    p.ball_pos = false;
-                    self.ball.is_carried = false;
-
-                    let speed = (PASS_DIST * GRAVITY).sqrt() / 1.12; // Empirical correction
-
-                    self.ball_throw = BallThrow {
-                        prev_x: self.ball.x,
-                        prev_y: self.ball.y,
-                        prev_z: self.ball.z,
-                        vx: speed * high_rad.cos() * direction_rad.cos(),
-                        vy: speed * high_rad.cos() * direction_rad.sin(),
-                        vz: speed * high_rad.sin(),
-                        active: true,
-                    };
-                }
+                p.ball_pos = false;
+                self.ball.is_carried = false;
+
+                let speed = (PASS_DIST * GRAVITY).sqrt() / 1.12; // Empirical correction
+
+                self.ball_throw = BallThrow {
+                    prev_x: self.ball.x,
+                    prev_y: self.ball.y,
+                    prev_z: self.ball.z,
+                    vx: speed * high_rad.cos() * direction_rad.cos(),
+                    vy: speed * high_rad.cos() * direction_rad.sin(),
+                    vz: speed * high_rad.sin(),
+                    exposure: PASS_EXPOSURE,
+                    spin: 0.0,
+                    bounces: 0,
+                    rolling: false,
+                    kind: ThrowKind::Pass,
+                    team,
+                    origin_x: p.x,
+                    origin_y: p.y,
+                    active: true,
+                };
+                self.clear_offside(if team == 'H' { 'A' } else { 'H' });
             }
         }
+    }
+
+    /// Rule on a pass once it is caught or hits the ground.
+    /// Returns true when the pass went forward and the referee stopped play.
+    pub fn check_forward_pass(&mut self) -> bool {
+        if self.ball_throw.kind != ThrowKind::Pass {
+            return false;
+        }
+        self.ball_throw.kind = ThrowKind::Loose;
 
-        if pass_invalid {
-            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-            print!("Invalid pass direction for player {} {}\n", team, number);
-            self.setup_scrum(if team == 'H' {'A'} else {'H'} , px, py);
-            return true;
+        let team = self.ball_throw.team;
+        let attack_north = (team == 'H') == (self.field.home_direction_try == 'N');
+        let travel = if attack_north {
+            self.ball.x - self.ball_throw.origin_x
+        } else {
+            self.ball_throw.origin_x - self.ball.x
+        };
+        if travel <= self.field.forward_pass_tolerance {
+            return false;
         }
-        false
+
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Forward pass by team {}, the ball went {:.1} m forward", team, travel);
+        self.play_advantage(if team == 'H' { 'A' } else { 'H' }, self.ball_throw.origin_x, self.ball_throw.origin_y, Sanction::Scrum)
     }
-}
\ No newline at end of file
+}
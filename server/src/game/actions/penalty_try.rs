@@ -0,0 +1,40 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::Sanction;
+
+impl GameState {
+    /// `team` has the ball close enough to the opponents' goal line for a try to be probable.
+    pub fn is_probable_try(&self, team: char, x: f32) -> bool {
+        let opponent = if team == 'H' { 'A' } else { 'H' };
+        let distance = (self.goal_line(opponent) - x) * self.attack_sign(team);
+        self.state.team == team && distance <= PENALTY_TRY_DISTANCE
+    }
+
+    /// Foul play against `team`: a penalty try when it stopped a probable try, a penalty advantage otherwise.
+    /// Returns true when the referee stops play straight away.
+    pub fn foul_play(&mut self, team: char, x: f32, y: f32) -> bool {
+        if self.is_probable_try(team, x) {
+            self.award_penalty_try(team);
+            return true;
+        }
+        self.play_advantage(team, x, y, Sanction::Penalty)
+    }
+
+    /// Foul play prevented a probable try: 7 points, no conversion, the offending team kicks off.
+    pub fn award_penalty_try(&mut self, team: char) {
+        self.advantage = None;
+        self.use_it_deadline = None;
+        self.ball.is_carried = false;
+        self.ball_throw.active = false;
+        self.ball_throw.rolling = false;
+        for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+            player.ball_pos = false;
+        }
+        let try_team = if team == 'H' { &mut self.home_team } else { &mut self.away_team };
+        try_team.try_scored += 1;
+        try_team.score += PENALTY_TRY_POINTS;
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Penalty try for team {}", team);
+        self.setup_restart(if team == 'H' { 'A' } else { 'H' });
+    }
+}
@@ -0,0 +1,124 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+
+impl GameState {
+    /// Quick throw-in by `number` of the team awarded the line out, taken at `x` on the touch line.
+    /// It has to be thrown straight or toward the own goal line, between the mark and the own goal line,
+    /// with the ball nobody else touched since it went into touch.
+    pub fn quick_throw_in(&mut self, number: usize, x: f32, angle: f32) {
+        let Some(quick) = self.quick_throw.take() else {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("No quick throw-in possible, the line out has to form");
+            return;
+        };
+        let team = quick.team;
+        let sign = self.attack_sign(team);
+        let touch_y = if quick.y == 0.5 { 1.0 } else { self.field.height as f32 + 1.0 };
+        let players = if team == 'H' { &self.home_team.players } else { &self.away_team.players };
+        let Some(thrower) = players.iter().find(|p| p.number == number) else {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Player {} {} is not on the field for a quick throw-in", team, number);
+            return;
+        };
+        if quick.touched_by.is_some_and(|touched_by| touched_by != (team, number)) {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Ball touched by another player, no quick throw-in for player {} {}", team, number);
+            return;
+        }
+
+        // The thrower has to fetch the ball before the window closes, counted from the ball going into touch
+        let distance = ((thrower.x - x).powi(2) + (thrower.y - touch_y).powi(2)).sqrt();
+        let arrival = self.time - quick.out_at + (distance / (thrower.speed / 3.6) * 100.0) as u64;
+        if arrival > QUICK_THROW_TIME {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Player {} {} is too late for a quick throw-in, the line out has to form", team, number);
+            return;
+        }
+
+        let own_goal_line = self.goal_line(team);
+        let in_place = (x - quick.x) * sign <= 0.0 && (x - own_goal_line) * sign >= 0.0;
+        let direction = angle.to_radians();
+        let into_field = if quick.y == 0.5 { direction.sin() > 0.0 } else { direction.sin() < 0.0 };
+        let forward = direction.cos() * sign > LINE_OUT_STRAIGHT_TOLERANCE.to_radians().sin();
+        if !in_place || !into_field || forward {
+            let opponent = if team == 'H' { 'A' } else { 'H' };
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Quick throw-in by player {} {} at {} not allowed, option for team {}", team, number, x, opponent);
+            self.state.name = "set-not-straight".to_string();
+            self.state.team = opponent;
+            return;
+        }
+
+        for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+            player.ball_pos = false;
+        }
+        let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+        if let Some(p) = players.iter_mut().find(|p| p.number == number) {
+            p.x = x;
+            p.y = touch_y;
+        }
+        self.ball.x = x;
+        self.ball.y = touch_y;
+        self.ball.is_carried = false;
+        self.launch_throw_in(team, angle);
+
+        self.state.name = "play".to_string();
+        self.state.team = team;
+        self.state.x = x;
+        self.state.y = touch_y;
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Quick throw-in by player {} {} at {}", team, number, x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::{ClientMessage, Command};
+
+    use crate::game::game_state::GameState;
+    use crate::game::models::QuickThrow;
+
+    // Ball in touch on the home side line at x = 60, home throws in
+    fn in_touch(touched_by: Option<(char, usize)>) -> GameState {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        game.state.name = "set-line_out".to_string();
+        game.state.team = 'H';
+        game.state.x = 60.0;
+        game.state.y = 0.5;
+        game.quick_throw = Some(QuickThrow { team: 'H', x: 60.0, y: 0.5, out_at: game.time, touched_by });
+        game
+    }
+
+    fn quick_throw(game: &mut GameState, number: usize, x: f32, y: f32) {
+        if let Some(p) = game.home_team.players.iter_mut().find(|p| p.number == number) {
+            p.x = x;
+            p.y = y;
+        }
+        let command = Command::QuickThrow { number, x: 58.0, angle: 90.0 };
+        game.dispatch(ClientMessage::Command { state: "set-line_out".to_string(), command });
+    }
+
+    #[test]
+    fn quick_throw_in_close_to_the_ball_is_taken() {
+        let mut game = in_touch(None);
+        quick_throw(&mut game, 9, 58.0, 3.0);
+        assert_eq!(game.state.name, "play");
+        assert!(game.ball_throw.active);
+    }
+
+    #[test]
+    fn quick_throw_in_too_late_forms_the_line_out() {
+        let mut game = in_touch(None);
+        quick_throw(&mut game, 9, 20.0, 35.0);
+        assert_eq!(game.state.name, "set-line_out");
+        assert!(game.quick_throw.is_none());
+    }
+
+    #[test]
+    fn quick_throw_in_of_a_ball_touched_by_another_player_is_refused() {
+        let mut game = in_touch(Some(('A', 4)));
+        quick_throw(&mut game, 9, 58.0, 3.0);
+        assert_eq!(game.state.name, "set-line_out");
+        assert!(game.quick_throw.is_none());
+    }
+}
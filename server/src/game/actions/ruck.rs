@@ -0,0 +1,210 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::*;
+
+impl GameState {
+    /// The first defender on their feet over the ball before the ruck is bound jackals for it.
+    /// Returns false once the ruck is bound, hands on the ball are then hands in the ruck.
+    pub fn jackal(&mut self, team: char, number: usize) -> bool {
+        let mut ruck = self.ruck.unwrap_or_default();
+        if team == self.state.team || ruck.bound {
+            return false;
+        }
+        let players = if team == 'H' { &self.home_team.players } else { &self.away_team.players };
+        if !players.iter().any(|p| p.number == number && !p.is_tackle && !p.is_tackler) {
+            return false;
+        }
+        if ruck.jackal.is_none() {
+            ruck.jackal = Some((team, number, self.time));
+            self.ruck = Some(ruck);
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Player {} {} jackals over the ball", team, number);
+        }
+        true
+    }
+
+    /// Weight over the ball decides the ruck: support binds it and clears the jackal,
+    /// a counter-ruck drives the ruck past the ball to win it, and a won ball has to be used.
+    pub fn update_ruck(&mut self) {
+        if self.state.name != "ruck" {
+            return;
+        }
+        let attack = self.state.team;
+        let defense = if attack == 'H' { 'A' } else { 'H' };
+        let sign = self.attack_sign(attack);
+        let mut ruck = self.ruck.unwrap_or_default();
+
+        let mut ruck_in = 0.0;
+        let mut ruck_front = 0.0;
+        let mut contest: Option<(usize, f32)> = None;
+        for (team, player) in self.home_team.players.iter().map(|p| ('H', p))
+        .chain(self.away_team.players.iter().map(|p| ('A', p))) {
+            let distance = ((player.x - self.state.x).powi(2) + (player.y - self.state.y).powi(2)).sqrt();
+            // The jackal has their hands on the ball, they do not drive
+            let is_jackal = ruck.jackal.is_some_and(|(t, n, _)| t == team && n == player.number);
+            if distance >= self.state.size || player.is_tackle || is_jackal {
+                continue;
+            }
+            if team == attack {
+                ruck_in += player.pound;
+            } else {
+                ruck_front += player.pound;
+                if contest.is_none_or(|(_, closest)| distance < closest) {
+                    contest = Some((player.number, distance));
+                }
+            }
+        }
+        ruck.clear = if ruck_in + ruck_front > 0.0 { ruck_in / (ruck_in + ruck_front) } else { 1.0 };
+
+        if !ruck.bound && ruck_in > 0.0 {
+            ruck.bound = true;
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            match ruck.jackal.take() {
+                Some((team, number, _)) => println!("Ruck bound by team {}, jackal {} {} cleared out", attack, team, number),
+                None => println!("Ruck bound by team {}", attack),
+            }
+        }
+
+        if let Some((team, number, since)) = ruck.jackal
+            && self.time >= since + JACKAL_TIME {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Jackal by player {} {} held, tackled player not releasing, penalty {}", team, number, team);
+            self.ruck = None;
+            self.use_it_deadline = None;
+            self.play_advantage(team, self.state.x, self.state.y, Sanction::Penalty);
+            return;
+        }
+
+        if ruck_front > ruck_in {
+            // Counter-ruck: the defenders drive the ruck back over the ball
+            let drive = RUCK_DRIVE * (ruck_front - ruck_in) / (ruck_front + ruck_in);
+            self.move_ruck(-sign * drive);
+            self.ruck = Some(ruck);
+            if (self.ball.x - self.state.x) * sign >= 0.0
+                && let Some((number, _)) = contest {
+                self.turnover_ruck(defense, number);
+            }
+            return;
+        }
+
+        if ruck.bound && self.use_it_deadline.is_none() {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Ruck won by team {}, use it", attack);
+            self.use_it_deadline = Some(self.time + USE_IT_TIME);
+        }
+        self.ruck = Some(ruck);
+
+        if self.use_it_deadline.is_some_and(|deadline| self.time >= deadline) {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Ball not used, turnover scrum for team {}", defense);
+            self.use_it_deadline = None;
+            self.ruck = None;
+            for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+                player.ball_pos = false;
+            }
+            self.setup_scrum(defense, self.state.x, self.state.y);
+        }
+    }
+
+    // Everybody in the ruck is driven with it, the ball stays on the ground
+    fn move_ruck(&mut self, dx: f32) {
+        let (x, y, size) = (self.state.x, self.state.y, self.state.size);
+        for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+            if ((player.x - x).powi(2) + (player.y - y).powi(2)).sqrt() < size {
+                player.x += dx;
+            }
+        }
+        self.state.x += dx;
+    }
+
+    // The ball is now on the side of `team`, the closest of them picks it up
+    fn turnover_ruck(&mut self, team: char, number: usize) {
+        let sign = self.attack_sign(team);
+        let players = if team == 'H' { &mut self.home_team.players } else { &mut self.away_team.players };
+        let Some(player) = players.iter_mut().find(|p| p.number == number) else {
+            return;
+        };
+        player.ball_pos = true;
+        self.ball.is_carried = true;
+        self.ball.z = 1.0;
+        self.ball.x = player.x + 0.5 * sign;
+        self.ball.y = player.y;
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Counter-ruck won, player {} {} picked up the ball from ruck", team, number);
+
+        self.state.name = "play".to_string();
+        self.state.team = team;
+        self.state.x = self.ball.x;
+        self.state.y = self.ball.y;
+        self.ruck = None;
+        self.use_it_deadline = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::game_state::GameState;
+    use crate::game::constants::*;
+    use crate::game::models::Sanction;
+
+    // Home ruck at x = 60, everybody away from it until placed
+    fn ruck() -> GameState {
+        let mut game = GameState::seeded(1, "play H 60 30");
+        for player in game.home_team.players.iter_mut().chain(game.away_team.players.iter_mut()) {
+            player.x = 20.0;
+            player.ball_pos = false;
+        }
+        game.setup_ruck(60.0, 30.0, 'H');
+        game
+    }
+
+    fn place(game: &mut GameState, team: char, number: usize, x: f32) {
+        let players = if team == 'H' { &mut game.home_team.players } else { &mut game.away_team.players };
+        if let Some(p) = players.iter_mut().find(|p| p.number == number) {
+            p.x = x;
+            p.y = 30.0;
+        }
+    }
+
+    #[test]
+    fn jackal_held_long_enough_wins_a_penalty() {
+        let mut game = ruck();
+        place(&mut game, 'A', 7, 60.0);
+        assert!(game.jackal('A', 7));
+        game.time += JACKAL_TIME;
+        game.update_ruck();
+        assert!(game.ruck.is_none());
+        assert!(game.advantage.is_some_and(|a| a.team == 'A' && a.sanction == Sanction::Penalty));
+    }
+
+    #[test]
+    fn support_binding_the_ruck_clears_the_jackal_and_starts_the_use_it_call() {
+        let mut game = ruck();
+        place(&mut game, 'A', 7, 60.0);
+        place(&mut game, 'H', 6, 59.8);
+        assert!(game.jackal('A', 7));
+        game.update_ruck();
+        assert!(game.ruck.is_some_and(|ruck| ruck.bound && ruck.jackal.is_none()));
+        assert!(!game.jackal('A', 7));
+
+        let deadline = game.use_it_deadline.unwrap();
+        game.time = deadline;
+        game.update_ruck();
+        assert_eq!(game.state.name, "scrum");
+        assert_eq!(game.state.team, 'A');
+    }
+
+    #[test]
+    fn heavier_counter_ruck_drives_over_the_ball_and_turns_it_over() {
+        let mut game = ruck();
+        place(&mut game, 'H', 6, 59.8);
+        place(&mut game, 'A', 4, 60.2);
+        place(&mut game, 'A', 5, 60.2);
+        for _ in 0..5 {
+            game.update_ruck();
+        }
+        assert_eq!(game.state.name, "play");
+        assert_eq!(game.state.team, 'A');
+        assert!(game.away_team.players.iter().any(|p| p.ball_pos));
+    }
+}
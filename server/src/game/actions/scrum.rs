@@ -0,0 +1,214 @@
+use rand::Rng;
+
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::*;
+
+impl GameState {
+    /// Crouch, bind and set are called one tick apart, then the scrum-half feeds.
+    /// Returns true while the packs are held and the ball is not yet in.
+    pub fn call_scrum(&mut self) -> bool {
+        let Some(mut scrum) = self.scrum else {
+            return false;
+        };
+        let call = match scrum.step {
+            ScrumStep::Crouch => "Crouch",
+            ScrumStep::Bind => "Bind",
+            ScrumStep::Set => "Set",
+            ScrumStep::Feed => {
+                self.feed_scrum();
+                return true;
+            },
+            ScrumStep::Fed => return false,
+        };
+        scrum.step = match scrum.step {
+            ScrumStep::Crouch => ScrumStep::Bind,
+            ScrumStep::Bind => ScrumStep::Set,
+            _ => ScrumStep::Feed,
+        };
+        self.scrum = Some(scrum);
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("{}", call);
+        true
+    }
+
+    // A crooked feed gives the opponents a free kick
+    fn feed_scrum(&mut self) {
+        let Some(mut scrum) = self.scrum else {
+            return;
+        };
+        let opponent = if scrum.team == 'H' { 'A' } else { 'H' };
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        if self.rng.random::<f32>() < SCRUM_CROOKED_FEED {
+            println!("Scrum feed by team {} not straight, free kick {}", scrum.team, opponent);
+            self.scrum = None;
+            self.play_advantage(opponent, self.state.x, self.state.y, Sanction::FreeKick);
+            return;
+        }
+        println!("Scrum fed by team {}", scrum.team);
+        scrum.step = ScrumStep::Fed;
+        self.scrum = Some(scrum);
+        self.ball.x = self.state.x;
+        self.ball.y = self.state.y;
+    }
+
+    /// Position of player `number` of `team` on the field, 0 when not on it.
+    /// Scrum roles follow the position, the order of the squad changes with cards and substitutions.
+    pub fn scrum_position(&self, team: char, number: i32) -> usize {
+        let players = if team == 'H' { &self.home_team.players } else { &self.away_team.players };
+        players.iter().find(|p| p.number == number as usize).map_or(0, |p| p.position)
+    }
+
+    /// Weight a player puts on the scrum, None for the backs: the eight forwards push.
+    pub fn scrum_pound(&self, team: char, number: i32) -> Option<f32> {
+        let players = if team == 'H' { &self.home_team.players } else { &self.away_team.players };
+        players.iter()
+            .find(|p| p.number == number as usize && (1..=8).contains(&p.position))
+            .map(|p| p.pound)
+    }
+
+    /// The hooker strikes for the ball just fed, the opposing one may win it against the head.
+    pub fn hook_ball(&mut self, team: char, number: i32) {
+        let Some(mut scrum) = self.scrum else {
+            return;
+        };
+        if scrum.step != ScrumStep::Fed || scrum.hooked.is_some() {
+            return;
+        }
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        if team == scrum.team {
+            // Settled at the end of the tick, the opposing hooker may still strike
+            println!("Hooker {} {} strikes for the ball", team, number);
+            return;
+        } else if self.rng.random::<f32>() < SCRUM_TIGHTHEAD_CHANCE {
+            println!("Hooker {} {} wins the ball against the head", team, number);
+            self.state.team = team;
+        } else {
+            println!("Hooker {} {} strikes for the ball and misses", team, number);
+            return;
+        }
+        scrum.hooked = Some(team);
+        self.scrum = Some(scrum);
+    }
+
+    /// Wheel and collapse of the scrum once the ball is in.
+    /// Returns true when the referee stopped the scrum.
+    pub fn update_scrum(&mut self, scrum_h_pound: f32, scrum_a_pound: f32) -> bool {
+        let Some(mut scrum) = self.scrum else {
+            return false;
+        };
+        if scrum.step != ScrumStep::Fed {
+            return false;
+        }
+        // Nobody struck against the head, the feeding hooker has it
+        if scrum.hooked.is_none() {
+            scrum.hooked = Some(scrum.team);
+        }
+
+        // The weaker front row is more likely to go down
+        let prop_pound = |players: &Vec<Player>| -> f32 {
+            players.iter().filter(|p| p.position == 1 || p.position == 3).map(|p| p.pound).sum::<f32>().max(1.0)
+        };
+        let (home_props, away_props) = (prop_pound(&self.home_team.players), prop_pound(&self.away_team.players));
+        let (strong, weak, ratio) = if home_props >= away_props {
+            ('H', 'A', home_props / away_props)
+        } else {
+            ('A', 'H', away_props / home_props)
+        };
+        if self.rng.random::<f32>() < SCRUM_COLLAPSE_CHANCE * ratio {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            if ratio >= SCRUM_DOMINANCE || scrum.resets >= SCRUM_MAX_RESETS {
+                println!("Scrum collapsed by team {}, penalty {}", weak, strong);
+                let props = if weak == 'H' { &self.home_team.players } else { &self.away_team.players };
+                if let Some(prop) = props.iter().find(|p| p.position == 1 || p.position == 3).map(|p| p.number) {
+                    self.record_collapse(weak, prop);
+                }
+                self.scrum = None;
+                self.foul_play(strong, self.state.x, self.state.y);
+            } else {
+                println!("Scrum collapsed, reset");
+                self.reset_scrum(scrum.team, scrum.resets + 1);
+            }
+            return true;
+        }
+
+        // A dominant pack turns the scrum, past 90° the other team feeds a new one
+        let total = (scrum_h_pound + scrum_a_pound).max(1.0);
+        scrum.wheel += SCRUM_WHEEL_RATE * (scrum_h_pound - scrum_a_pound).abs() / total + self.rng.random::<f32>() * SCRUM_WHEEL_DRIFT;
+        if scrum.wheel > SCRUM_WHEEL_LIMIT {
+            let team = if self.state.team == 'H' { 'A' } else { 'H' };
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Scrum wheeled past 90°, new scrum for team {}", team);
+            self.reset_scrum(team, 0);
+            return true;
+        }
+        self.scrum = Some(scrum);
+        false
+    }
+
+    fn reset_scrum(&mut self, team: char, resets: u32) {
+        self.setup_scrum(team, self.state.x, self.state.y);
+        if let Some(scrum) = self.scrum.as_mut() {
+            scrum.resets = resets;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::{Action, ClientMessage, Command, PlayerAction};
+
+    use crate::game::game_state::GameState;
+    use crate::game::models::ScrumStep;
+
+    // Scrum chosen by home from a penalty at x = 60
+    fn scrum(seed: u64) -> GameState {
+        let mut game = GameState::seeded(seed, "set-penalty H 60 30");
+        game.dispatch(ClientMessage::Command { state: "set-penalty".to_string(), command: Command::Scrum });
+        game
+    }
+
+    // Every player of `teams` stays, the forwards push
+    fn stay(game: &mut GameState, teams: &[char]) {
+        let actions = (1..=15)
+            .flat_map(|number| teams.iter().map(move |&team| PlayerAction { team, number, action: Action::Stay }))
+            .collect();
+        game.dispatch(ClientMessage::Actions { state: "scrum".to_string(), actions });
+    }
+
+    fn step(game: &GameState) -> Option<ScrumStep> {
+        game.scrum.map(|scrum| scrum.step)
+    }
+
+    #[test]
+    fn referee_calls_crouch_bind_set_before_the_feed() {
+        let mut game = scrum(4);
+        assert_eq!(step(&game), Some(ScrumStep::Crouch));
+        for expected in [ScrumStep::Bind, ScrumStep::Set, ScrumStep::Feed, ScrumStep::Fed] {
+            stay(&mut game, &['H', 'A']);
+            assert_eq!(step(&game), Some(expected));
+        }
+        assert!(game.ball.x == game.state.x && game.ball.y == game.state.y);
+    }
+
+    #[test]
+    fn packs_of_equal_weight_hold_the_scrum_straight() {
+        let mut game = scrum(4);
+        for _ in 0..6 {
+            stay(&mut game, &['H', 'A']);
+        }
+        assert_eq!(game.state.name, "scrum");
+        assert!(game.scrum.is_some_and(|scrum| scrum.step == ScrumStep::Fed && scrum.wheel < 10.0));
+    }
+
+    #[test]
+    fn pack_that_does_not_push_is_wheeled_and_loses_the_feed() {
+        let mut game = scrum(4);
+        for _ in 0..6 {
+            stay(&mut game, &['H']);
+        }
+        assert_eq!(game.state.name, "scrum");
+        assert_eq!(game.state.team, 'A');
+        assert!(game.scrum.is_some_and(|scrum| scrum.team == 'A' && scrum.step != ScrumStep::Fed));
+    }
+}
@@ -1,20 +1,36 @@
 use crate::game::game_state::GameState;
 use crate::game::constants::*;
+use crate::game::models::{Ruck, Scrum, ScrumStep};
 
 impl GameState {
     pub fn setup_ruck(&mut self, x: f32, y: f32, team: char) {
         self.ball.is_carried = false;
-        self.ball.x = x - 0.5;
+        self.ball.x = x - 0.5 * self.attack_sign(team);
         self.ball.y = y;
         self.ball.z = 0.0;
         self.state.name = "ruck".to_string();
         self.state.x = x;
         self.state.y = y;
         self.state.team = team;
+        self.state.size = RUCK_SIZE;
+        self.ruck = Some(Ruck::default());
+        self.use_it_deadline = None;
         print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
         print!("Ruck formed at position {} {}\n", x, y);
     }
 
+    // The ball carrier stays on their feet and keeps the ball
+    pub fn setup_maul(&mut self, x: f32, y: f32, team: char) {
+        self.state.name = "maul".to_string();
+        self.state.x = x;
+        self.state.y = y;
+        self.state.team = team;
+        self.state.size = MAUL_SIZE;
+        self.use_it_deadline = None;
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Maul formed at position {} {}", x, y);
+    }
+
     pub fn setup_scrum(&mut self, team: char, x: f32, y: f32) {
         let x = x.clamp(self.field.try_size as f32 + 6.0, (self.field.width + self.field.try_size) as f32 - 4.0);
         let y = y.clamp(6.0, self.field.height as f32 - 6.0);
@@ -67,7 +83,7 @@ impl GameState {
         };
 
         for (num, dx, dy) in scrum_offsets(-1.0) {
-            if let Some(p) = north_team.iter_mut().find(|p: &&mut crate::game::models::Player| p.number == num) {
+            if let Some(p) = north_team.iter_mut().find(|p: &&mut crate::game::models::Player| p.position == num) {
                 p.ball_pos = false;
                 p.x = (self.state.x + dx).clamp(self.field.try_size as f32 + 1.0, (self.field.width + self.field.try_size) as f32 + 1.0);
                 if num == 9 && self.state.y > (self.field.height / 2) as f32 + 1.0 {
@@ -86,7 +102,7 @@ impl GameState {
         }
 
         for (num, dx, dy) in scrum_offsets(1.0) {
-            if let Some(p) = south_team.iter_mut().find(|p| p.number == num) {
+            if let Some(p) = south_team.iter_mut().find(|p| p.position == num) {
                 p.ball_pos = false;
                 p.x = (self.state.x + dx).clamp(self.field.try_size as f32 + 1.0, (self.field.width + self.field.try_size) as f32 + 1.0);
                 if num == 9 && self.state.y <= (self.field.height / 2) as f32 + 1.0 {
@@ -127,9 +143,9 @@ impl GameState {
         }
 
         let mut i = 0;
-        for p in attack_team.iter_mut().filter(|p| p.number >= 10) {
+        for p in attack_team.iter_mut().filter(|p| p.position >= 10) {
             p.ball_pos = false;
-            if p.number == 11 || p.number == 14 {
+            if p.position == 11 || p.position == 14 {
                 continue;
             }
             p.x = pts.get(i).unwrap().0.clamp(self.field.try_size as f32 + 1.0, (self.field.width + self.field.try_size) as f32 + 1.0);
@@ -138,9 +154,9 @@ impl GameState {
         }
 
         i = 0;
-        for p in defense_team.iter_mut().filter(|p| p.number >= 10) {
+        for p in defense_team.iter_mut().filter(|p| p.position >= 10) {
             p.ball_pos = false;
-            if p.number == 11 || p.number == 14 {
+            if p.position == 11 || p.position == 14 {
                 continue;
             }
             p.x = (self.state.x + defense_line).clamp(self.field.try_size as f32 + 1.0, (self.field.width + self.field.try_size) as f32 + 1.0);
@@ -150,6 +166,7 @@ impl GameState {
 
         print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
         print!("Setting up scrum for team {} at ({}, {})\n", self.state.team, self.state.x, self.state.y);
+        self.scrum = Some(Scrum { team, step: ScrumStep::Crouch, hooked: None, resets: 0, wheel: 0.0 });
     }
 
     // PENALTY
@@ -161,9 +178,64 @@ impl GameState {
         self.state.size = 0.0;
     }
 
+    // Free kick awarded on a mark or a minor scrum offence, the team picks a kick or a scrum
+    pub fn setup_free_kick_choice(&mut self, team: char, x: f32, y: f32) {
+        self.state.name = "set-free-kick".to_string();
+        self.state.team = team;
+        self.state.x = x;
+        self.state.y = y;
+        self.state.size = 0.0;
+    }
+
     pub fn setup_free_kick(&mut self, number: usize, direction: f32, high: f32) {
+        self.line_up_kick("free-kick", number, direction, high);
+    }
+
+    // Penalty kicked to touch, the kicking team keeps the throw of the line out
+    pub fn setup_touch_kick(&mut self, number: usize, direction: f32, high: f32) {
+        self.line_up_kick("touch-kick", number, direction, high);
+    }
+
+    // Penalty tapped on the mark, the opponents have to retire 10 m
+    pub fn setup_quick_tap(&mut self, number: usize) {
+        let team = self.state.team;
+        let sign = self.attack_sign(team);
+        let (tap_team, opponents) = if team == 'H' {
+            (&mut self.home_team.players, &mut self.away_team.players)
+        } else {
+            (&mut self.away_team.players, &mut self.home_team.players)
+        };
+        if !tap_team.iter().any(|p| p.number == number) {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Player {} {} is not on the field to tap the penalty", team, number);
+            return;
+        }
         self.time += 25;
-        self.state.name = "free-kick".to_string();
+        for player in tap_team.iter_mut().chain(opponents.iter_mut()) {
+            player.ball_pos = false;
+        }
+        if let Some(tapper) = tap_team.iter_mut().find(|p| p.number == number) {
+            tapper.ball_pos = true;
+            tapper.x = self.state.x;
+            tapper.y = self.state.y;
+        }
+        for player in opponents.iter_mut() {
+            if (player.x - self.state.x) * sign < PENALTY_RETIRE {
+                player.x = self.state.x + PENALTY_RETIRE * sign;
+            }
+        }
+        self.ball.is_carried = true;
+        self.ball.x = self.state.x + 0.5 * sign;
+        self.ball.y = self.state.y;
+        self.ball.z = 1.0;
+        self.state.name = "play".to_string();
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Quick tap penalty by player {} {}", team, number);
+    }
+
+    fn line_up_kick(&mut self, name: &str, number: usize, direction: f32, high: f32) {
+        self.time += 25;
+        self.state.name = name.to_string();
         let (kick_team, receive_team, kick_direction) = if self.state.team == 'H' {
             (&mut self.home_team.players, &mut self.away_team.players, self.field.home_direction_try)
         } else {
@@ -172,8 +244,8 @@ impl GameState {
         let diff = if kick_direction == 'N' { 0.5 } else { -0.5 };
 
         print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-        print!("Setting up free kick for team {} by player {} to {} and {} high\n",
-            self.state.team, number, direction, high);
+        print!("Setting up {} for team {} by player {} to {} and {} high\n",
+            name.replace('-', " "), self.state.team, number, direction, high);
 
         self.ball.is_carried = true;
         self.ball.x = self.state.x + diff;
@@ -199,15 +271,15 @@ impl GameState {
         }
 
         for (index, &num) in POSITIONS.iter().enumerate() {
-            if let Some(p) = kick_team.iter_mut().find(|p| p.number == num && p.number != number) {
-                let sup_ten = if p.number > 10 && p.number % 2 == 0 { 3.0 } else { 0.0 };
+            if let Some(p) = kick_team.iter_mut().find(|p| p.position == num && p.number != number) {
+                let sup_ten = if p.position > 10 && p.position % 2 == 0 { 3.0 } else { 0.0 };
                 if self.state.y < (self.field.height / 2) as f32 + 1.0 {
                     p.y = 6.0 + (index as f32 * 3.0 - sup_ten);
                 } else {
                     p.y = (self.field.height - ((POSITIONS.len() - 2) * 3) - 6) as f32 + (index as f32 * 3.0 - sup_ten);
                 }
             }
-            if let Some(p) = receive_team.iter_mut().find(|p| p.number == num) {
+            if let Some(p) = receive_team.iter_mut().find(|p| p.position == num) {
                 if self.state.y < (self.field.height / 2) as f32 + 1.0 {
                     p.y = 6.0 + ((POSITIONS.len() - 2) * 3) as f32 - index as f32 * 3.0;
                     if index == POSITIONS.len() - 1 {
@@ -266,15 +338,15 @@ impl GameState {
         }
 
         for (index, &num) in POSITIONS.iter().enumerate() {
-            if let Some(p) = kick_team.iter_mut().find(|p| p.number == num && p.number != number) {
-                let sup_ten = if p.number > 10 && p.number % 2 == 0 { 3.0 } else { 0.0 };
+            if let Some(p) = kick_team.iter_mut().find(|p| p.position == num && p.number != number) {
+                let sup_ten = if p.position > 10 && p.position % 2 == 0 { 3.0 } else { 0.0 };
                 if self.state.y < (self.field.height / 2) as f32 + 1.0 {
                     p.y = 6.0 + (index as f32 * 3.0 - sup_ten);
                 } else {
                     p.y = (self.field.height - ((POSITIONS.len() - 2) * 3) - 6) as f32 + (index as f32 * 3.0 - sup_ten);
                 }
             }
-            if let Some(p) = receive_team.iter_mut().find(|p| p.number == num) {
+            if let Some(p) = receive_team.iter_mut().find(|p| p.position == num) {
                 if self.state.y < (self.field.height / 2) as f32 + 1.0 {
                     p.y = 6.0 + ((POSITIONS.len() - 1) * 3) as f32 - index as f32 * 3.0;
                 } else {
@@ -330,15 +402,15 @@ impl GameState {
         }
 
         for (index, &num) in POSITIONS.iter().enumerate() {
-            if let Some(p) = kick_team.iter_mut().find(|p| p.number == num && p.number != number) {
-                let sup_ten = if p.number > 10 && p.number % 2 == 0 { 3.0 } else { 0.0 };
+            if let Some(p) = kick_team.iter_mut().find(|p| p.position == num && p.number != number) {
+                let sup_ten = if p.position > 10 && p.position % 2 == 0 { 3.0 } else { 0.0 };
                 if self.state.y < (self.field.height / 2) as f32 + 1.0 {
                     p.y = 6.0 + (index as f32 * 3.0 - sup_ten);
                 } else {
                     p.y = (self.field.height - ((POSITIONS.len() - 2) * 3) - 6) as f32 + (index as f32 * 3.0 - sup_ten);
                 }
             }
-            if let Some(p) = receive_team.iter_mut().find(|p| p.number == num) {
+            if let Some(p) = receive_team.iter_mut().find(|p| p.position == num) {
                 if self.state.y < (self.field.height / 2) as f32 + 1.0 {
                     p.y = 6.0 + ((POSITIONS.len() - 1) * 3) as f32 - index as f32 * 3.0;
                 } else {
@@ -358,7 +430,23 @@ impl GameState {
         self.state.x = self.field.try_size as f32 + self.field.width as f32 / 2.0 + 1.0;
         self.state.y = self.field.height as f32 / 2.0 + 1.0;
         self.state.size = 0.0;
+        self.line_up_restart(team);
+    }
+
+    // 22 m or goal-line drop-out, kicked from the line by the defending team
+    pub fn setup_drop_out(&mut self, team: char, x: f32) {
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        println!("Setting up drop-out for team {} at {}", team, x);
+        self.state.name = "drop-out".to_string();
+        self.state.team = team;
+        self.state.x = x;
+        self.state.y = self.field.height as f32 / 2.0 + 1.0;
+        self.state.size = 0.0;
+        self.line_up_restart(team);
+    }
 
+    // Kicker on the mark, teammates behind, opponents spread at least 10 m away
+    fn line_up_restart(&mut self, team: char) {
         self.ball.is_carried = true;
         self.ball.x = self.state.x;
         self.ball.y = self.state.y;
@@ -367,6 +455,7 @@ impl GameState {
         self.ball_throw.vy = 0.0;
         self.ball_throw.vz = 0.0;
         self.ball_throw.active = false;
+        self.ball_throw.rolling = false;
         self.ball_throw.prev_x = 0.0;
         self.ball_throw.prev_y = 0.0;
         self.ball_throw.prev_z = 0.0;
@@ -456,11 +545,12 @@ impl GameState {
         }
     }
 
-    pub fn setup_line_out(&mut self, number: usize, h_line: Vec<&str>, a_line: Vec<&str>) {
+    pub fn setup_line_out(&mut self, number: usize, h_line: Vec<usize>, a_line: Vec<usize>) {
         print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
         print!("Setting up an line out for team {} at {} {}", self.state.team, self.state.x, self.state.y);
-        print!(" size {} and aline H{} A{}\n", number, h_line.concat(), a_line.concat());
+        print!(" size {} and aline H{:?} A{:?}\n", number, h_line, a_line);
         self.state.name = "line_out".to_string();
+        self.quick_throw = None;
         self.state.size = if self.state.y == 0.5 { 0.0 } else { 16.0 };
         let (throw_team, face_team, direction, t_line, f_line) = if self.state.team == 'H' {
             (&mut self.home_team.players,
@@ -476,11 +566,16 @@ impl GameState {
             &h_line
         )
         };
-        throw_team[1].ball_pos = true;
-        throw_team[1].x = self.state.x;
-        throw_team[1].y = self.state.y;
-        face_team[1].x = self.state.x + if direction == 'N' { 5.0 } else { -5.0 };
-        face_team[1].y = if self.state.y == 0.5 { 3.5 } else { self.field.height as f32 - 1.5 };
+        // The hookers throw in and mark the throw, the first player on the field stands in for a missing one
+        if let Some(thrower) = throw_team.iter().position(|p| p.position == 2).or((!throw_team.is_empty()).then_some(0)) {
+            throw_team[thrower].ball_pos = true;
+            throw_team[thrower].x = self.state.x;
+            throw_team[thrower].y = self.state.y;
+        }
+        if let Some(marker) = face_team.iter_mut().find(|p| p.position == 2) {
+            marker.x = self.state.x + if direction == 'N' { 5.0 } else { -5.0 };
+            marker.y = if self.state.y == 0.5 { 3.5 } else { self.field.height as f32 - 1.5 };
+        }
         self.ball.is_carried = true;
         self.ball.x = self.state.x;
         let diff = if self.state.y == 0.5 { 0.5 } else { -0.5 };
@@ -490,12 +585,13 @@ impl GameState {
         self.ball_throw.vy = 0.0;
         self.ball_throw.vz = 0.0;
         self.ball_throw.active = false;
+        self.ball_throw.rolling = false;
         self.ball_throw.prev_x = self.ball.x;
         self.ball_throw.prev_y = self.ball.y;
         self.ball_throw.prev_z = self.ball.z;
 
         for (index, t) in t_line.iter().enumerate() {
-            if let Some(p) = throw_team.iter_mut().find(|p| p.number == t.parse::<usize>().unwrap()) {
+            if let Some(p) = throw_team.iter_mut().find(|p| p.number == *t) {
                 p.ball_pos = false;
                 p.x = self.state.x + if direction == 'N' { -1.0 } else { 1.0 };
                 if self.state.y == 0.5 {
@@ -507,7 +603,7 @@ impl GameState {
         }
 
         for (index, f) in f_line.iter().enumerate() {
-            if let Some(p) = face_team.iter_mut().find(|p| p.number == f.parse::<usize>().unwrap()) {
+            if let Some(p) = face_team.iter_mut().find(|p| p.number == *f) {
                 p.ball_pos = false;
                 p.x = self.state.x + if direction == 'N' { 1.0 } else { -1.0 };
                 if self.state.y == 0.5 {
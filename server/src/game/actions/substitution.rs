@@ -0,0 +1,80 @@
+use protocol::Substitution;
+
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+use crate::game::models::*;
+
+impl GameState {
+    // Players can only be replaced while the ball is dead
+    pub fn is_stoppage(&self) -> bool {
+        self.phase == Phase::HalfTime || self.is_ball_dead()
+    }
+
+    /// Substitutions asked by a client with a `sub` message.
+    pub fn substitute(&mut self, substitutions: Vec<Substitution>) {
+        if !self.is_stoppage() {
+            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+            println!("Substitutions refused, the ball is not dead");
+            return;
+        }
+        for Substitution { team, off, on } in substitutions {
+            self.replace_player(team, off, on);
+        }
+    }
+
+    /// Planned substitutions of the field settings, made at the first stoppage after `switch_time`.
+    pub fn update_substitutions(&mut self) {
+        if !self.field.is_switch || self.switch_done || !self.is_stoppage() {
+            return;
+        }
+        if self.time < self.field.switch_time as u64 * TIME_PER_MINUTE {
+            return;
+        }
+        self.switch_done = true;
+        for (off, on) in self.field.switch_home.clone() {
+            self.replace_player('H', off, on);
+        }
+        for (off, on) in self.field.switch_away.clone() {
+            self.replace_player('A', off, on);
+        }
+    }
+
+    fn replace_player(&mut self, team: char, off: usize, on: usize) {
+        let squad = if team == 'H' { &mut self.home_team } else { &mut self.away_team };
+
+        let refusal = if squad.replacements >= MAX_REPLACEMENTS {
+            Some(format!("all {} replacements are used", MAX_REPLACEMENTS))
+        } else if squad.replaced.contains(&on) {
+            Some(format!("player {} already left the field and cannot come back", on))
+        } else if !squad.bench.iter().any(|p| p.number == on) {
+            Some(format!("player {} is not on the bench", on))
+        } else {
+            match squad.players.iter().find(|p| p.number == off) {
+                None => Some(format!("player {} is not on the field", off)),
+                Some(p) if p.position <= 3 && !FRONT_ROW_REPLACEMENTS.contains(&on) => {
+                    Some(format!("front row player {} can only be replaced by {:?}", off, FRONT_ROW_REPLACEMENTS))
+                },
+                Some(_) => None,
+            }
+        };
+
+        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+        if let Some(reason) = refusal {
+            println!("Substitution {} {} for {} refused: {}", team, on, off, reason);
+            return;
+        }
+
+        let bench_index = squad.bench.iter().position(|p| p.number == on).unwrap();
+        let mut replacement = squad.bench.remove(bench_index);
+        let player = squad.players.iter_mut().find(|p| p.number == off).unwrap();
+        replacement.x = player.x;
+        replacement.y = player.y;
+        replacement.position = player.position;
+        replacement.ball_pos = player.ball_pos;
+        *player = replacement;
+
+        squad.replaced.push(off);
+        squad.replacements += 1;
+        println!("Substitution {}: {} replaces {} ({}/{})", team, on, off, squad.replacements, MAX_REPLACEMENTS);
+    }
+}
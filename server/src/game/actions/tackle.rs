@@ -1,4 +1,7 @@
+use rand::Rng;
+
 use crate::game::game_state::GameState;
+use crate::game::constants::*;
 
 impl GameState {
     pub fn tackle(&mut self, team: char, number: i32) {
@@ -12,6 +15,10 @@ impl GameState {
         } else {
             (&mut self.away_team.players, &mut self.home_team.players)
         };
+        // A teammate bound on the ball carrier keeps them on their feet
+        let supported = opponents.iter().find(|o| o.ball_pos).is_some_and(|o| {
+            opponents.iter().any(|t| !t.ball_pos && ((t.x - o.x).powi(2) + (t.y - o.y).powi(2)).sqrt() < MAUL_BIND)
+        });
 
         if let Some(p) = players.iter_mut().find(|p| p.ball_pos) {
             print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
@@ -22,8 +29,15 @@ impl GameState {
             if let Some(o) = opponents.iter_mut().find(|p| p.ball_pos) {
                 let distance = ((p.x - o.x).powi(2) + (p.y - o.y).powi(2)).sqrt();
                 if distance < 1.2 {
-                    let is_successful = rand::random::<f32>() * 100.0 < p.p_tackle;
-                    if is_successful {
+                    let is_successful = self.rng.random::<f32>() * 100.0 < p.p_tackle;
+                    if is_successful && supported {
+                        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                        println!("Ball carrier held up by player {} {}", team, number);
+                        let maul_team = if team == 'H' { 'A' } else { 'H' };
+                        let maul_x = o.x;
+                        let maul_y = o.y;
+                        self.setup_maul(maul_x, maul_y, maul_team);
+                    } else if is_successful {
                         p.is_tackle = true;
                         print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
                         print!("Tackle successful by player {} {}\n", team, number);
@@ -1,4 +1,5 @@
 use crate::game::game_state::GameState;
+use crate::game::models::Sanction;
 
 impl GameState {
 
@@ -37,6 +38,7 @@ impl GameState {
                 }
                 self.state.name = "play".to_string();
                 self.state.team = team;
+                self.scrum = None;
                 self.ball.y = p.y;
                 self.state.y = self.ball.y;
                 self.ball.z = 1.0;
@@ -46,8 +48,8 @@ impl GameState {
 
     //RUCK
 
-    pub fn try_catch_ball_in_ruck(&mut self, team: char, number: i32) -> bool {
-        let is_offside = self.check_offside_ruck();
+    /// Only a player behind the ruck may pick the ball up, hands in the ruck give the opponents a penalty.
+    pub fn try_catch_ball_in_ruck(&mut self, team: char, number: i32) {
         let players = if team == 'H' {
             &mut self.home_team.players
         } else {
@@ -59,11 +61,6 @@ impl GameState {
             let distance_ruck = ((p.x - self.state.x).powi(2) + (p.y - self.state.y).powi(2)).sqrt();
 
             if distance_ball < 1.0 && distance_ruck >= 1.0 {
-                if is_offside {
-                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                    print!("Offside penalty for {}\n", self.state.team);
-                    return true;
-                }
                 p.ball_pos = true;
                 self.ball.is_carried = true;
                 self.ball_throw.active = false;
@@ -85,37 +82,20 @@ impl GameState {
                 }
                 self.state.name = "play".to_string();
                 self.state.team = team;
+                self.ruck = None;
+                self.use_it_deadline = None;
                 self.ball.y = p.y;
                 self.state.y = self.ball.y;
                 self.ball.z = 1.0;
-            }
-        }
-        false
-    }
-
-    fn check_offside_ruck(&self) -> bool {
-        let players = if self.state.team == 'H' {
-            &self.away_team.players
-        } else {
-            &self.home_team.players
-        };
-        let diff = if (self.state.team == 'H' && self.field.home_direction_try == 'N') ||
-                            (self.state.team == 'A' && self.field.home_direction_try == 'S') {
-            1.0
-        } else {
-            -1.0
-        };
-
-        for player in players.iter() {
-            let distance = ((player.x - self.state.x).powi(2) + (player.y - self.state.y).powi(2)).sqrt();
-            if distance >= self.state.size &&
-            ((diff == 1.0 && player.x < self.state.x + diff) ||
-            (diff == -1.0 && player.x > self.state.x + diff)) {
+            } else if distance_ball < 1.0 {
+                if self.jackal(team, number as usize) {
+                    return;
+                }
                 print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                print!("Player {} is offside\n", player.number);
-                return true;
+                println!("Player {} {} uses hands in the ruck", team, number);
+                self.record_infringement(team, number as usize);
+                self.play_advantage(if team == 'H' { 'A' } else { 'H' }, self.state.x, self.state.y, Sanction::Penalty);
             }
         }
-        return false;
     }
 }
\ No newline at end of file
@@ -1,3 +1,5 @@
+use rand::Rng;
+
 use crate::game::game_state::GameState;
 use crate::game::constants::*;
 
@@ -10,18 +12,27 @@ impl GameState {
             self.ball_throw.prev_y = self.ball.y;
             self.ball_throw.prev_z = self.ball.z;
 
-            self.ball.x += self.ball_throw.vx * DT;
-            self.ball.y += self.ball_throw.vy * DT;
-            self.ball.z += self.ball_throw.vz * DT;
-            self.ball_throw.vz -= GRAVITY * DT; // gravity effect
+            if self.ball_throw.rolling {
+                self.roll_ball();
+            } else {
+                self.ball.x += self.ball_throw.vx * DT;
+                self.ball.y += self.ball_throw.vy * DT;
+                self.ball.z += self.ball_throw.vz * DT;
+                self.ball_throw.vz -= GRAVITY * DT; // gravity effect
+                self.apply_drag();
+                self.apply_wind();
+
+                if self.ball.z <= 0.0 {
+                    self.ball.z = 0.0;
+                    if self.check_forward_pass() {
+                        return;
+                    }
+                    self.bounce_ball();
+                }
+            }
 
             self.state.x = self.ball.x;
             self.state.y = self.ball.y;
-
-            if self.ball.z <= 0.0 {
-                self.ball.z = 0.0;
-                self.ball_throw.active = false;
-            }
         }
     }
 
@@ -31,17 +42,23 @@ impl GameState {
             print!("Strange process happen, function call out of context\n");
             return;
         }
+        let mut fumble = None;
+        let mut caught = None;
+        // A ball still in flight is harder to hold than one lying on the ground
+        let in_flight = self.ball_throw.active && !self.ball_throw.rolling;
         if !self.ball.is_carried && self.ball.z <= 3.5 {
+            let risk = (self.field.weather / 2) as f32 + if in_flight { CATCH_ERROR } else { 0.0 };
             for (team, player) in
             self.home_team.players.iter_mut().map(|p| ('H', p))
             .chain(self.away_team.players.iter_mut().map(|p| ('A', p))) {
                 let distance = ((player.x - self.ball.x).powi(2) + (player.y - self.ball.y).powi(2)).sqrt();
                 if distance < 1.0 && self.ball.z <= player.size + 50.0 { // 50 cm player arm
-                    let is_successful = rand::random::<f32>() * 100.0 > (self.field.weather / 2) as f32;
+                    let is_successful = self.rng.random::<f32>() * 100.0 > risk;
                     if !is_successful {
                         print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                        print!("Player {} failed to pick up the ball due to weather\n", player.number);
-                        continue;
+                        print!("Player {} failed to pick up the ball\n", player.number);
+                        fumble = Some((team, player.number, player.x, player.y));
+                        break;
                     }
                     player.ball_pos = true;
                     self.ball.is_carried = true;
@@ -67,10 +84,23 @@ impl GameState {
                     self.ball.y = player.y;
                     self.state.y = self.ball.y;
                     self.ball.z = 1.0;
+                    caught = Some((team, player.number));
                     break;
                 }
             }
         }
+
+        // The pass is ruled on as soon as a player gets a hand on it
+        if (caught.is_some() || fumble.is_some()) && self.check_forward_pass() {
+            return;
+        }
+        if let Some((team, number)) = caught
+            && self.check_offside(team, number as i32, None) {
+            return;
+        }
+        if let Some((team, number, x, y)) = fumble {
+            self.fumble(team, number, x, y, in_flight);
+        }
     }
 
     // SCRUM
@@ -85,7 +115,7 @@ impl GameState {
         if scrum_front > scrum_in {
             let diff_ratio = (scrum_front - scrum_in) / (scrum_front + scrum_in) * 20.0;
             let reverse_probability = diff_ratio.clamp(0.0, 1.0);
-            if rand::random::<f32>() < reverse_probability {
+            if self.rng.random::<f32>() < reverse_probability {
                 direction = -direction;
                 print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
                 print!("Scrum contest\n");
@@ -3,6 +3,72 @@ pub const DT: f32 = 0.25;
 pub const PASS_HEIGHT: f32 = 1.0;
 pub const PASS_DIST: f32 = 20.0;
 pub const SCRUM_SIZE: f32 = 4.2;
+pub const RUCK_SIZE: f32 = 1.0;
+pub const MAUL_SIZE: f32 = 1.5;
+pub const MAUL_BIND: f32 = 1.5;
+pub const MAUL_SPEED: f32 = 0.5; // meters per tick for a one-sided push
+pub const MAUL_MIN_ADVANCE: f32 = 0.05;
+pub const MAUL_COLLAPSE_RATIO: f32 = 1.5;
+pub const MAUL_COLLAPSE_CHANCE: f32 = 0.3;
+pub const JACKAL_TIME: u64 = 50; // a jackal holding on this long wins a penalty
+pub const RUCK_DRIVE: f32 = 1.0; // meters per tick for a one-sided counter-ruck
+pub const USE_IT_TIME: u64 = 500;
+pub const SCRUM_CROOKED_FEED: f32 = 0.05; // chance the scrum-half does not feed straight
+pub const SCRUM_TIGHTHEAD_CHANCE: f32 = 0.2; // chance the opposing hooker steals the feed
+pub const SCRUM_COLLAPSE_CHANCE: f32 = 0.03; // per tick, times the ratio of the props' weight
+pub const SCRUM_DOMINANCE: f32 = 1.15; // props' weight ratio above which a collapse is penalised
+pub const SCRUM_MAX_RESETS: u32 = 2;
+pub const SCRUM_WHEEL_RATE: f32 = 50.0; // degrees per tick for a fully dominant pack
+pub const SCRUM_WHEEL_DRIFT: f32 = 3.0; // random degrees per tick
+pub const SCRUM_WHEEL_LIMIT: f32 = 90.0;
+pub const NOT_STRAIGHT_SCRUM: f32 = 15.0;
+pub const QUICK_THROW_TIME: u64 = 300;
+pub const MARK_TIME: u64 = 25; // the mark is called on the catch or the tick after
+pub const QUICK_THROW_BALL_DISTANCE: f32 = 5.0; // beyond it a new ball is needed
+pub const LINE_OUT_STRAIGHT_TOLERANCE: f32 = 10.0; // degrees
+pub const LINE_OUT_THROW_SPEED: f32 = 11.0;
+pub const LINE_OUT_THROW_ELEVATION: f32 = 30.0; // degrees
+pub const LINE_OUT_RELEASE_HEIGHT: f32 = 2.0;
+pub const LINE_OUT_CATCH_DISTANCE: f32 = 2.0;
+pub const LINE_OUT_CONTEST_LUCK: f32 = 0.3;
+pub const REACH_RATIO: f32 = 1.33; // standing reach over height
+pub const JUMP_HEIGHT: f32 = 0.6;
+pub const JUMP_TIME: u64 = 50;
+pub const LIFT_HEIGHT: f32 = 0.5;
+pub const LIFT_DISTANCE: f32 = 2.5;
+pub const LIFT_TIME: u64 = 100;
+pub const SCRUM_OFFSIDE: f32 = 5.0;
+pub const LINE_OUT_OFFSIDE: f32 = 10.0;
+pub const LINE_OUT_CORRIDOR: f32 = 2.0;
 pub const WALK_SPEED: f32 = 6.4;
 pub const POSITIONS: &[usize] = &[11, 15, 13, 9, 7, 5, 3, 1, 2, 4, 6, 8, 10, 12, 14];
+pub const KMH_TO_MS: f32 = 1000.0 / 3600.0;
+pub const WIND_DRAG: f32 = 0.06;
+pub const GUST_FREQUENCY: f32 = 0.9;
+pub const KICK_EXPOSURE: f32 = 1.0;
+pub const PASS_EXPOSURE: f32 = 0.3;
+pub const AIR_DRAG: f32 = 0.005;
+pub const RESTITUTION: f32 = 0.55;
+pub const MIN_BOUNCE_SPEED: f32 = 1.5;
+pub const MAX_BOUNCES: u32 = 6;
+pub const BOUNCE_DEVIATION: f32 = 25.0; // degrees
+pub const BOUNCE_FRICTION: f32 = 0.7;
+pub const SPIN_GRIP: f32 = 0.05;
+pub const KICK_SPIN: f32 = 3.0;
+pub const ROLLING_FRICTION: f32 = 2.5; // m/s²
+pub const STOP_SPEED: f32 = 0.3;
+pub const CATCH_ERROR: f32 = 5.0; // % of balls in flight dropped
+pub const KNOCK_SPEED: f32 = 3.0;
+pub const KNOCK_SPREAD: f32 = 60.0; // degrees either side of the incoming ball
+pub const ADVANTAGE_GAIN: f32 = 10.0; // meters gained past the mark end the advantage
+pub const TIME_PER_MINUTE: u64 = 6000;
+pub const MAX_REPLACEMENTS: u32 = 8;
+pub const PENALTY_RETIRE: f32 = 10.0; // meters the opponents retire on a quick tap
+pub const PENALTY_TRY_DISTANCE: f32 = 5.0; // meters from the goal line where foul play stops a probable try
+pub const PENALTY_TRY_POINTS: u32 = 7;
+pub const SIN_BIN_TIME: u64 = 10 * TIME_PER_MINUTE;
+pub const PLAYER_INFRINGEMENTS_CARD: u32 = 3; // penalty offences by one player before a yellow card
+pub const TEAM_INFRINGEMENTS_CARD: u32 = 8; // every 8th team penalty offence is carded
+pub const COLLAPSES_RED_CARD: u32 = 3; // collapses by one player before a direct red card
+pub const FRONT_ROW_REPLACEMENTS: &[usize] = &[16, 17, 18];
 pub const RUNNING_SPEED_FACTOR: f32 = 1000.0 / 3600.0 * DT;
@@ -17,11 +17,16 @@ impl GameState {
         if self.state.name == "ruck" {
             drawable.set_state(self.state.x, self.state.y, self.state.name.clone(), self.state.size);
         }
+        if self.state.name == "maul" {
+            drawable.set_state(self.state.x, self.state.y, self.state.name.clone(), self.state.size);
+        }
         if self.state.name == "line_out" {
             drawable.set_state(self.state.x, self.state.y, self.state.name.clone(), self.state.size);
         }
         drawable.home_score = self.home_team.score as usize;
         drawable.away_score = self.away_team.score as usize;
+        drawable.home_cards = self.home_team.sin_bin.iter().map(|_| 'Y').chain(self.home_team.sent_off.iter().map(|_| 'R')).collect();
+        drawable.away_cards = self.away_team.sin_bin.iter().map(|_| 'Y').chain(self.away_team.sent_off.iter().map(|_| 'R')).collect();
         drawable.set_time(self.time);
         return drawable;
     }
@@ -1,2 +1,2 @@
 pub mod drawable;
-pub mod position;
\ No newline at end of file
+pub mod snapshot;
\ No newline at end of file
@@ -0,0 +1,42 @@
+use protocol::{Card, PlayerState, Position, RuckState, Snapshot};
+
+use crate::game::game_state::GameState;
+
+impl GameState {
+    pub fn snapshot(&self) -> Snapshot {
+        let mut players = Vec::new();
+        for player in self.home_team.players.iter() {
+            players.push(PlayerState { team: 'H', number: player.number, x: player.x, y: player.y, has_ball: player.ball_pos });
+        }
+        for player in self.away_team.players.iter() {
+            players.push(PlayerState { team: 'A', number: player.number, x: player.x, y: player.y, has_ball: player.ball_pos });
+        }
+        let mut cards = Vec::new();
+        for (team, squad) in [('H', &self.home_team), ('A', &self.away_team)] {
+            for (player, until) in squad.sin_bin.iter() {
+                cards.push(Card { team, number: player.number, kind: 'Y', until: *until });
+            }
+            for player in squad.sent_off.iter() {
+                cards.push(Card { team, number: player.number, kind: 'R', until: 0 });
+            }
+        }
+        let ruck = if self.state.name == "ruck" {
+            let use_it = self.use_it_deadline.map_or(0, |deadline| deadline.saturating_sub(self.time));
+            Some(RuckState { clear: self.ruck.unwrap_or_default().clear, use_it })
+        } else {
+            None
+        };
+        Snapshot {
+            name: self.state.name.clone(),
+            team: self.state.team,
+            time: self.time,
+            home_score: self.home_team.score,
+            away_score: self.away_team.score,
+            ball: Position { x: self.ball.x, y: self.ball.y },
+            players,
+            cards,
+            ruck,
+            events: Vec::new(),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use rand::rngs::StdRng;
+
 use crate::game::models::*;
 
 #[derive(Clone)]
@@ -10,4 +12,16 @@ pub struct GameState {
     pub away_team: Team,
     pub ball: Ball,
     pub ball_throw: BallThrow,
+    pub phase: Phase,
+    pub siren: bool,
+    pub kick_off_team: char,
+    pub switch_done: bool,
+    pub advantage: Option<Advantage>,
+    pub use_it_deadline: Option<u64>,
+    pub quick_throw: Option<QuickThrow>,
+    pub fair_catch: Option<(char, usize, u64)>,
+    pub scrum: Option<Scrum>,
+    pub ruck: Option<Ruck>,
+    pub seed: u64,
+    pub rng: StdRng, // every random draw of the match, replayed from the seed
 }
@@ -1,3 +1,6 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
 use crate::game::game_state::GameState;
 use crate::game::models::*;
 
@@ -21,32 +24,66 @@ impl GameState {
             switch_away: Vec::new(),
             wind_strength: 0,
             wind_direction: 0,
+            wind_gust: 0,
             weather: 0,
+            forward_pass_tolerance: 1.0,
+            advantage_ticks: 40,
+            line_22: 22,
+            half_duration: 40,
+            extra_duration: 0,
         };
         let time = 0;
         let addr = String::new();
         let home_team = Team {
             players: Vec::new(),
             bench: Vec::new(),
+            replaced: Vec::new(),
+            replacements: 0,
             score: 0,
             try_scored: 0,
             transformation: 0,
             penalty: 0,
             drop: 0,
+            penalties_conceded: 0,
+            sin_bin: Vec::new(),
+            sent_off: Vec::new(),
         };
         let away_team = Team {
             players: Vec::new(),
             bench: Vec::new(),
+            replaced: Vec::new(),
+            replacements: 0,
             score: 0,
             try_scored: 0,
             transformation: 0,
             penalty: 0,
             drop: 0,
+            penalties_conceded: 0,
+            sin_bin: Vec::new(),
+            sent_off: Vec::new(),
         };
         let ball: Ball = Ball { x: 50.0, y: 35.0, z: 1.0, is_carried: false };
-        let ball_throw = BallThrow { prev_x: 0.0, prev_y: 0.0, prev_z: 0.0, vx: 0.0, vy: 0.0, vz: 0.0, active: false };
+        let ball_throw = BallThrow { prev_x: 0.0, prev_y: 0.0, prev_z: 0.0, vx: 0.0, vy: 0.0, vz: 0.0, exposure: 0.0, spin: 0.0, bounces: 0, rolling: false, kind: ThrowKind::Loose, team: 'H', origin_x: 0.0, origin_y: 0.0, active: false };
+        let phase = Phase::FirstHalf;
+        let siren = false;
+        let kick_off_team = 'H';
+        let switch_done = false;
+        let advantage = None;
+        let use_it_deadline = None;
+        let quick_throw = None;
+        let fair_catch = None;
+        let scrum = None;
+        let ruck = None;
+        let seed = 0;
+        let rng = StdRng::seed_from_u64(seed);
+
+        Self { state, field, time, addr, home_team, away_team, ball, ball_throw, phase, siren, kick_off_team, switch_done, advantage, use_it_deadline, quick_throw, fair_catch, scrum, ruck, seed, rng }
+    }
 
-        Self { state, field, time, addr, home_team, away_team, ball, ball_throw }
+    /// Restart the random draws from `seed`, the same seed and the same decisions replay the same match.
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
     }
 
     //INIT
@@ -56,32 +93,25 @@ impl GameState {
         self.addr = addr;
         // Initialize field
         let field_info: Vec<&str> = field.split('_').collect();
-        self.field.width = field_info.get(0)
-            .and_then(|s| s.split('=').nth(1))
+        self.field.width = field_setting(&field_info, "fw")
             .and_then(|v| v.parse().ok())
             .unwrap_or(100);
-        self.field.height = field_info.get(1)
-            .and_then(|s| s.split('=').nth(1))
+        self.field.height = field_setting(&field_info, "fh")
             .and_then(|v| v.parse().ok())
             .unwrap_or(70);
-        self.field.try_size = field_info.get(2)
-            .and_then(|s| s.split('=').nth(1))
+        self.field.try_size = field_setting(&field_info, "ft")
             .and_then(|v| v.parse().ok())
             .unwrap_or(10);
-        self.field.home_direction_try = field_info.get(3)
-            .and_then(|s| s.split('=').nth(1))
+        self.field.home_direction_try = field_setting(&field_info, "sht")
             .and_then(|v| v.chars().next())
             .unwrap_or('N');
-        self.field.is_switch = field_info.get(4)
-        .and_then(|s| s.split('=').nth(1))
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(false);
-        self.field.switch_time = field_info.get(5)
-            .and_then(|s| s.split('=').nth(1))
+        self.field.is_switch = field_setting(&field_info, "isw")
+            .map(|v| v == "t" || v == "true")
+            .unwrap_or(false);
+        self.field.switch_time = field_setting(&field_info, "swt")
             .and_then(|v| v.parse().ok())
             .unwrap_or(40);
-        self.field.switch_home = field_info.get(6)
-            .and_then(|s| s.split('=').nth(1))
+        self.field.switch_home = field_setting(&field_info, "swh")
             .map(|v| {
                 v.split('/')
                     .filter_map(|pair| {
@@ -91,8 +121,7 @@ impl GameState {
                     .collect::<Vec<(usize, usize)>>()
             })
             .unwrap_or_else(Vec::new);
-        self.field.switch_away = field_info.get(7)
-            .and_then(|s: &&str| s.split('=').nth(1))
+        self.field.switch_away = field_setting(&field_info, "swa")
             .map(|v| {
                 v.split('/')
                     .filter_map(|pair| {
@@ -102,18 +131,36 @@ impl GameState {
                     .collect::<Vec<(usize, usize)>>()
             })
             .unwrap_or_else(Vec::new);
-        self.field.wind_strength = field_info.get(8)
-            .and_then(|s| s.split('=').nth(1))
+        self.field.wind_strength = field_setting(&field_info, "wis")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        self.field.wind_direction = field_setting(&field_info, "wid")
             .and_then(|v| v.parse().ok())
             .unwrap_or(0);
-        self.field.wind_direction = field_info.get(9)
-            .and_then(|s| s.split('=').nth(1))
+        self.field.wind_gust = field_setting(&field_info, "wg")
             .and_then(|v| v.parse().ok())
             .unwrap_or(0);
-        self.field.weather = field_info.get(10)
-            .and_then(|s| s.split('=').nth(1))
+        self.field.weather = field_setting(&field_info, "wt")
             .and_then(|v| v.parse().ok())
             .unwrap_or(0);
+        self.field.forward_pass_tolerance = field_setting(&field_info, "fpt")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1.0);
+        self.field.advantage_ticks = field_setting(&field_info, "adt")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(40);
+        self.field.line_22 = field_setting(&field_info, "l22")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(22);
+        self.field.half_duration = field_setting(&field_info, "hd")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(40);
+        self.field.extra_duration = field_setting(&field_info, "etd")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        if let Some(seed) = field_setting(&field_info, "seed").and_then(|v| v.parse().ok()) {
+            self.reseed(seed);
+        }
 
         // Initialize home players
         for (i, player) in home_players.iter().enumerate() {
@@ -145,9 +192,9 @@ impl GameState {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10.0);
             if i >= 15 {
-                self.home_team.bench.push(Player { x, y, number: (i + 1), ball_pos: false, size, pound, speed, foot, p_foot, p_tackle, is_tackle: false, is_tackler: false });
+                self.home_team.bench.push(Player { x, y, number: (i + 1), position: 0, ball_pos: false, size, pound, speed, foot, p_foot, p_tackle, is_tackle: false, is_tackler: false, is_offside: false, jump_height: 0.0, jump_until: 0, infringements: 0, yellow_cards: 0, collapses: 0 });
             } else {
-                self.home_team.players.push(Player { x, y, number: (i + 1), ball_pos: false, size, pound, speed, foot, p_foot, p_tackle, is_tackle:false, is_tackler: false });
+                self.home_team.players.push(Player { x, y, number: (i + 1), position: (i + 1), ball_pos: false, size, pound, speed, foot, p_foot, p_tackle, is_tackle:false, is_tackler: false, is_offside: false, jump_height: 0.0, jump_until: 0, infringements: 0, yellow_cards: 0, collapses: 0 });
             }
         }
 
@@ -181,9 +228,9 @@ impl GameState {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10.0);
             if i >= 15 {
-                self.home_team.bench.push(Player { x, y, number: (i + 1), ball_pos: false, size, pound, speed, foot, p_foot, p_tackle, is_tackle:false, is_tackler: false });
+                self.away_team.bench.push(Player { x, y, number: (i + 1), position: 0, ball_pos: false, size, pound, speed, foot, p_foot, p_tackle, is_tackle:false, is_tackler: false, is_offside: false, jump_height: 0.0, jump_until: 0, infringements: 0, yellow_cards: 0, collapses: 0 });
             } else {
-                self.away_team.players.push(Player { x, y, number: (i + 1), ball_pos: false, size, pound, speed, foot, p_foot, p_tackle, is_tackle:false, is_tackler: false });
+                self.away_team.players.push(Player { x, y, number: (i + 1), position: (i + 1), ball_pos: false, size, pound, speed, foot, p_foot, p_tackle, is_tackle:false, is_tackler: false, is_offside: false, jump_height: 0.0, jump_until: 0, infringements: 0, yellow_cards: 0, collapses: 0 });
             }
         }
 
@@ -216,6 +263,7 @@ impl GameState {
                 self.state.size = part.get(4)
                     .and_then(|s| s.parse().ok())
                     .unwrap_or(0.0);
+                self.kick_off_team = self.state.team;
 
                 if self.state.name == "ruck" {
                     let tackle = part.get(5)
@@ -319,5 +367,31 @@ impl GameState {
 
         print!("Initialized game state: {}\n", self.state.name);
         print!("Ball position: {} {} {}\n", self.ball.x, self.ball.y, self.ball.z);
+        println!("Random seed: {}", self.seed);
+    }
+}
+
+/// Value of a `key=value` field setting, wherever it sits in the line.
+fn field_setting<'a>(field_info: &[&'a str], key: &str) -> Option<&'a str> {
+    field_info.iter()
+        .filter_map(|s| s.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+#[cfg(test)]
+impl GameState {
+    /// Match of fifteen identical players a side on a standard field, seeded, starting in `state`.
+    pub fn seeded(seed: u64, state: &str) -> GameState {
+        let player = "s=185_p=100_ms=27_fd=25_%f=60_%t=70".to_string();
+        let mut game = GameState::new();
+        game.reseed(seed);
+        game.initialize(
+            "fw=100_fh=70_ft=10_l22=22_sht=N_hd=40".to_string(),
+            vec![player.clone(); 15],
+            vec![player; 15],
+            state.to_string(),
+            "test".to_string(),
+        );
+        game
     }
-}
\ No newline at end of file
+}
@@ -4,5 +4,6 @@ pub mod constants;
 pub mod models;
 pub mod init;
 pub mod actions;
+pub mod physics;
 pub mod state;
 pub mod extract;
\ No newline at end of file
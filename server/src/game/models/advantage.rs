@@ -0,0 +1,17 @@
+/// Restart owed to the non-offending side once advantage is over.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sanction {
+    Scrum,
+    FreeKick,
+    Penalty,
+}
+
+/// Advantage played after an infringement, `team` is the non-offending side.
+#[derive(Clone, Copy)]
+pub struct Advantage {
+    pub team: char,
+    pub x: f32,
+    pub y: f32,
+    pub until: u64,
+    pub sanction: Sanction,
+}
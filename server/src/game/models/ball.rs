@@ -6,6 +6,14 @@ pub struct Ball {
     pub is_carried: bool, // true if the ball is being carried by a player
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum ThrowKind {
+    Kick,
+    Pass,
+    LineOut,
+    Loose, // fumbled, or a pass already ruled on
+}
+
 #[derive(Clone, Copy)]
 pub struct BallThrow {
     pub prev_x: f32,
@@ -14,5 +22,13 @@ pub struct BallThrow {
     pub vx: f32,
     pub vy: f32,
     pub vz: f32,
+    pub exposure: f32, // share of the wind felt by the ball, 1.0 for a kick
+    pub spin: f32, // end over end turns per second, 0.0 for a spiral
+    pub bounces: u32,
+    pub rolling: bool,
+    pub kind: ThrowKind,
+    pub team: char, // team that released the ball
+    pub origin_x: f32, // release point
+    pub origin_y: f32,
     pub active: bool,
 }
\ No newline at end of file
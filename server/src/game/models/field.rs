@@ -3,6 +3,7 @@ pub struct Field {
     pub width: usize,
     pub height: usize,
     pub try_size: usize,
+    pub line_22: usize, // meters from the goal line
     pub home_direction_try: char,
     pub is_switch: bool,
     pub switch_time: usize,
@@ -10,5 +11,10 @@ pub struct Field {
     pub switch_away: Vec<(usize, usize)>,
     pub wind_strength: usize,
     pub wind_direction: usize, // 0-360 degrees
+    pub wind_gust: usize, // km/h added on top of wind_strength at the peak of a gust
     pub weather: usize, // 0-100 for rain,
+    pub forward_pass_tolerance: f32, // meters a pass may drift forward with the runner's momentum
+    pub advantage_ticks: u64, // ticks the referee waits before the advantage is over
+    pub half_duration: u64, // minutes
+    pub extra_duration: u64, // minutes, 0 for no extra time
 }
\ No newline at end of file
@@ -3,9 +3,19 @@ pub mod team;
 pub mod ball;
 pub mod field;
 pub mod state;
+pub mod phase;
+pub mod advantage;
+pub mod quick_throw;
+pub mod scrum;
+pub mod ruck;
 
 pub use player::Player;
 pub use team::Team;
-pub use ball::{Ball, BallThrow};
+pub use ball::{Ball, BallThrow, ThrowKind};
 pub use field::Field;
-pub use state::State;
\ No newline at end of file
+pub use state::State;
+pub use phase::Phase;
+pub use advantage::{Advantage, Sanction};
+pub use quick_throw::QuickThrow;
+pub use scrum::{Scrum, ScrumStep};
+pub use ruck::Ruck;
\ No newline at end of file
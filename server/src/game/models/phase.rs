@@ -0,0 +1,8 @@
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Phase {
+    FirstHalf,
+    HalfTime,
+    SecondHalf,
+    ExtraTime,
+    FullTime,
+}
\ No newline at end of file
@@ -3,6 +3,7 @@ pub struct Player {
     pub x: f32,
     pub y: f32,
     pub number: usize,
+    pub position: usize, // 1-15 on the field, 0 on the bench
     pub ball_pos: bool,
     pub size: f32,
     pub pound: f32,
@@ -12,4 +13,10 @@ pub struct Player {
     pub p_tackle: f32,
     pub is_tackle: bool,
     pub is_tackler: bool,
+    pub is_offside: bool, // in front of a teammate who kicked
+    pub jump_height: f32, // meters gained jumping and lifted in a line out
+    pub jump_until: u64,
+    pub infringements: u32, // penalty offences since the last card
+    pub yellow_cards: u32,
+    pub collapses: u32, // scrums and mauls brought down
 }
\ No newline at end of file
@@ -0,0 +1,9 @@
+/// Quick throw-in still open to `team` after the ball went into touch at `x`, `y`.
+#[derive(Clone, Copy)]
+pub struct QuickThrow {
+    pub team: char,
+    pub x: f32,
+    pub y: f32,
+    pub out_at: u64, // game time when the ball went into touch
+    pub touched_by: Option<(char, usize)>, // player holding the ball since it went into touch
+}
@@ -0,0 +1,7 @@
+/// Contest over the ball on the ground after a tackle.
+#[derive(Clone, Copy, Default)]
+pub struct Ruck {
+    pub bound: bool, // a teammate of the tackled player is over the ball, too late to jackal
+    pub jackal: Option<(char, usize, u64)>, // first defender over the ball and when they got there
+    pub clear: f32, // share of the weight over the ball on the side of the team in possession
+}
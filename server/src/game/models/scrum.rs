@@ -0,0 +1,19 @@
+/// Calls of the referee before the ball is fed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScrumStep {
+    Crouch,
+    Bind,
+    Set,
+    Feed,
+    Fed,
+}
+
+/// Engagement of the current scrum.
+#[derive(Clone, Copy)]
+pub struct Scrum {
+    pub team: char, // team feeding the ball
+    pub step: ScrumStep,
+    pub hooked: Option<char>,
+    pub resets: u32,
+    pub wheel: f32, // degrees the scrum turned since the feed
+}
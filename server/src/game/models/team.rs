@@ -4,9 +4,14 @@ use crate::game::models::*;
 pub struct Team {
     pub players: Vec<Player>,
     pub bench: Vec<Player>,
+    pub replaced: Vec<usize>, // numbers of players who left the field
+    pub replacements: u32,
     pub score: u32,
     pub try_scored: u32,
     pub transformation: u32,
     pub penalty: u32,
     pub drop: u32,
+    pub penalties_conceded: u32,
+    pub sin_bin: Vec<(Player, u64)>, // yellow carded players and the time they come back
+    pub sent_off: Vec<Player>,
 }
\ No newline at end of file
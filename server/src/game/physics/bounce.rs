@@ -0,0 +1,54 @@
+use rand::Rng;
+
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+
+impl GameState {
+    /// The ball hits the ground: it bounces off at a random angle, or starts rolling once too slow.
+    pub fn bounce_ball(&mut self) {
+        let wet = self.field.weather as f32 / 100.0;
+        let impact = -self.ball_throw.vz;
+
+        if impact < MIN_BOUNCE_SPEED || self.ball_throw.bounces >= MAX_BOUNCES {
+            self.ball_throw.vz = 0.0;
+            self.ball_throw.rolling = true;
+            return;
+        }
+        self.ball_throw.bounces += 1;
+
+        // An oval ball never comes back the same way, more so when it spins end over end
+        let spread = BOUNCE_DEVIATION * (1.0 + self.ball_throw.spin / KICK_SPIN);
+        let deviation = ((self.rng.random::<f32>() * 2.0 - 1.0) * spread).to_radians();
+        let (sin, cos) = deviation.sin_cos();
+        let vx = self.ball_throw.vx * cos - self.ball_throw.vy * sin;
+        let vy = self.ball_throw.vx * sin + self.ball_throw.vy * cos;
+
+        // Topspin grips the grass and kicks the ball forward, a wet ground takes more energy
+        let grip = BOUNCE_FRICTION * (1.0 - wet * 0.5) + self.ball_throw.spin * SPIN_GRIP;
+        let height = RESTITUTION * (1.0 - wet * 0.4) * (0.5 + self.rng.random::<f32>());
+        self.ball_throw.vx = vx * grip;
+        self.ball_throw.vy = vy * grip;
+        self.ball_throw.vz = impact * height;
+        self.ball_throw.spin *= 0.5;
+    }
+
+    // Rolling slows down faster on a wet ground
+    pub fn roll_ball(&mut self) {
+        self.ball.x += self.ball_throw.vx * DT;
+        self.ball.y += self.ball_throw.vy * DT;
+        self.ball.z = 0.0;
+
+        let speed = (self.ball_throw.vx.powi(2) + self.ball_throw.vy.powi(2)).sqrt();
+        let friction = ROLLING_FRICTION * (1.0 + self.field.weather as f32 / 100.0) * DT;
+        if speed <= friction || speed < STOP_SPEED {
+            self.ball_throw.vx = 0.0;
+            self.ball_throw.vy = 0.0;
+            self.ball_throw.rolling = false;
+            self.ball_throw.active = false;
+            return;
+        }
+        let factor = (speed - friction) / speed;
+        self.ball_throw.vx *= factor;
+        self.ball_throw.vy *= factor;
+    }
+}
@@ -0,0 +1,14 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+
+impl GameState {
+    // Air resistance grows with the square of the ball speed
+    pub fn apply_drag(&mut self) {
+        let speed = (self.ball_throw.vx.powi(2) + self.ball_throw.vy.powi(2) + self.ball_throw.vz.powi(2)).sqrt();
+        let factor = (1.0 - AIR_DRAG * speed * DT).max(0.0);
+
+        self.ball_throw.vx *= factor;
+        self.ball_throw.vy *= factor;
+        self.ball_throw.vz *= factor;
+    }
+}
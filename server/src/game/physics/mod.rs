@@ -0,0 +1,3 @@
+pub mod wind;
+pub mod drag;
+pub mod bounce;
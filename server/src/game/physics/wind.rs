@@ -0,0 +1,29 @@
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+
+impl GameState {
+    /// Wind velocity in m/s at the current time, gusts included.
+    /// The direction uses the same angles as the actions, `wid=0` means no wind.
+    pub fn wind_velocity(&self) -> (f32, f32) {
+        if self.field.wind_direction == 0 {
+            return (0.0, 0.0);
+        }
+        let t = self.time as f32 / 100.0;
+        // Two slow waves out of phase so gusts do not repeat too regularly
+        let gust = self.field.wind_gust as f32 * ((t * GUST_FREQUENCY).sin() * (t * GUST_FREQUENCY * 0.37).sin()).abs();
+        let speed = (self.field.wind_strength as f32 + gust) * KMH_TO_MS;
+        let direction = (self.field.wind_direction as f32).to_radians();
+
+        (speed * direction.cos(), speed * direction.sin())
+    }
+
+    // The ball is pushed by the wind, harder when it is more exposed.
+    // The slowing down of the ball itself is left to the air drag, so no wind changes nothing.
+    pub fn apply_wind(&mut self) {
+        let (wx, wy) = self.wind_velocity();
+        let factor = WIND_DRAG * self.ball_throw.exposure * DT;
+
+        self.ball_throw.vx += wx * factor;
+        self.ball_throw.vy += wy * factor;
+    }
+}
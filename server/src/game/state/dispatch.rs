@@ -0,0 +1,136 @@
+use protocol::{ClientMessage, Command, PlayerAction};
+
+use crate::game::game_state::GameState;
+use crate::game::models::Phase;
+
+// Handlers of the states answered with player actions with whether the ball is dead in that state,
+// and of the `set-` states answered with a command, where the ball is always dead
+type ActionHandler = (&'static str, fn(&mut GameState, Vec<PlayerAction>), bool);
+type CommandHandler = (&'static str, fn(&mut GameState, Command));
+
+pub const ACTION_HANDLERS: &[ActionHandler] = &[
+    ("start", GameState::play, true),
+    ("play", GameState::play, false),
+    ("restart", GameState::play, true),
+    ("drop-out", GameState::play, true),
+    ("free-kick", GameState::play, false),
+    ("touch-kick", GameState::play, false),
+    ("penalty-kick", GameState::play, false),
+    ("transformation-kick", GameState::play, true),
+    // Dead while the packs set up, see `is_ball_dead`
+    ("scrum", GameState::scrum, false),
+    ("ruck", GameState::ruck, false),
+    ("maul", GameState::maul, false),
+    ("line_out", GameState::line_out, false),
+];
+pub const COMMAND_HANDLERS: &[CommandHandler] = &[
+    ("set-penalty", GameState::set_penalty),
+    ("set-transformation", GameState::set_transformation),
+    ("set-line_out", GameState::set_line_out),
+    ("set-not-straight", GameState::set_not_straight),
+    ("set-free-kick", GameState::set_free_kick),
+];
+
+impl GameState {
+
+    /// Route a typed client message to the handler of the state it was sent for.
+    /// Returns false when no handler exists for that state.
+    pub fn dispatch(&mut self, message: ClientMessage) -> bool {
+        // Substitutions do not move the game forward
+        if let ClientMessage::Substitute { substitutions } = message {
+            self.substitute(substitutions);
+            return true;
+        }
+
+        // Between periods any message restarts the game, after full time nothing does
+        match self.phase {
+            Phase::HalfTime => {
+                self.start_period();
+                return true;
+            },
+            Phase::FullTime => return true,
+            _ => {},
+        }
+
+        let handled = match message {
+            ClientMessage::Actions { state, actions } => {
+                match ACTION_HANDLERS.iter().find(|(name, _, _)| *name == state) {
+                    Some((_, handler, _)) => {
+                        handler(self, actions);
+                        true
+                    },
+                    None => false,
+                }
+            },
+            ClientMessage::Command { state, command } => {
+                match COMMAND_HANDLERS.iter().find(|(name, _)| *name == state) {
+                    Some((_, handler)) => {
+                        handler(self, command);
+                        true
+                    },
+                    None => false,
+                }
+            },
+            _ => false,
+        };
+
+        if handled {
+            self.update_clock();
+            self.update_sin_bin();
+            self.update_substitutions();
+        }
+        handled
+    }
+}
+#[cfg(test)]
+mod tests {
+    use protocol::{Action, ClientMessage, Command, PlayerAction, Snapshot};
+
+    use crate::game::game_state::GameState;
+
+    // Every player chases the ball, tackles the carrier within reach and the carrier kicks ahead
+    fn decide(game: &GameState) -> ClientMessage {
+        let state = game.state.name.clone();
+        let command = match state.as_str() {
+            "set-penalty" => Some(Command::PenaltyKick { number: 10, direction: 0.0, high: 35.0 }),
+            "set-free-kick" | "set-not-straight" => Some(Command::Scrum),
+            "set-transformation" => Some(Command::Transformation { number: 10, distance: 10.0, direction: 0.0, high: 30.0 }),
+            "set-line_out" => Some(Command::LineOut { number: 5, home_line: vec![1, 4, 6, 7, 5], away_line: vec![1, 4, 6, 7, 5] }),
+            _ => None,
+        };
+        if let Some(command) = command {
+            return ClientMessage::Command { state, command };
+        }
+
+        let mut actions = vec![];
+        for (team, players) in [('H', &game.home_team.players), ('A', &game.away_team.players)] {
+            for p in players {
+                let distance = ((game.ball.x - p.x).powi(2) + (game.ball.y - p.y).powi(2)).sqrt();
+                let action = if p.ball_pos {
+                    Action::Kick { direction: if team == 'H' { 10.0 } else { 190.0 }, high: 40.0 }
+                } else if game.ball.is_carried && distance < 1.2 {
+                    Action::Tackle
+                } else {
+                    Action::Run { direction: (game.ball.y - p.y).atan2(game.ball.x - p.x).to_degrees() }
+                };
+                actions.push(PlayerAction { team, number: p.number, action });
+            }
+        }
+        ClientMessage::Actions { state, actions }
+    }
+
+    fn replay(seed: u64) -> Vec<Snapshot> {
+        let mut game = GameState::seeded(seed, "set-penalty H 80 26");
+        let mut snapshots = vec![game.snapshot()];
+        for _ in 0..300 {
+            game.dispatch(decide(&game));
+            snapshots.push(game.snapshot());
+        }
+        snapshots
+    }
+
+    #[test]
+    fn same_seed_and_inputs_replay_the_same_match() {
+        assert_eq!(replay(42), replay(42));
+    }
+}
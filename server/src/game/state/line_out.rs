@@ -1,52 +1,63 @@
+use protocol::{Action, PlayerAction};
+
 use crate::game::game_state::GameState;
+use crate::game::models::ThrowKind;
 
 impl GameState {
 
-    pub fn line_out(&mut self, input: String) {
+    pub fn line_out(&mut self, actions: Vec<PlayerAction>) {
         self.time += 25;
+        let mut lifts = Vec::new();
 
-        let actions: Vec<&str> = input.split('\n').collect();
-        for action in actions {
-            if let Some((player, action)) = action.split_once(':') {
-                let tmp = action.chars().nth(0).unwrap_or('\0');
-                match tmp {
-                    'R' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let direction = action[1..].parse().unwrap_or(0.0);
-                        // println!("Player {} {} runs {}", team, number, direction);
-                        self.run_line_out(team, number, direction, true);
-                    },
-                    'W' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let direction = action[1..].parse().unwrap_or(0.0);
-                        // println!("Player {} {} walk {}", team, number, direction);
-                        self.run_line_out(team, number, direction, false);
-                    },
-                    'T' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let angle = action[1..].parse().unwrap_or(0.0);
-                        self.throw_from_line_out(team, number, angle);
-                    },
-                    'J' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        self.jump_line_out(team, number);
-                    },
-                    'L' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        self.lift_line_out(team, number);
-                    },
-                    'S' => continue,
-                    _ => {
-                        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                        print!("Unknown action in line out: {}\n", action);
-                    },
-                }
+        for PlayerAction { team, number, action } in actions {
+            let number = number as i32;
+            match action {
+                Action::Run { direction } => {
+                    // println!("Player {} {} runs {}", team, number, direction);
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    self.run_line_out(team, number, direction, true);
+                },
+                Action::Walk { direction } => {
+                    // println!("Player {} {} walk {}", team, number, direction);
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    self.run_line_out(team, number, direction, false);
+                },
+                Action::Throw { angle } => {
+                    self.throw_from_line_out(team, number, angle);
+                    if self.state.name != "line_out" {
+                        return;
+                    }
+                },
+                Action::Jump => {
+                    self.jump_line_out(team, number);
+                },
+                Action::Lift => {
+                    // Lifters need their jumper in the air first
+                    lifts.push((team, number));
+                },
+                Action::Stay => continue,
+                _ => {
+                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                    println!("Unknown action in line out: {}", action.serialize());
+                },
             }
         }
+
+        for (team, number) in lifts {
+            self.lift_line_out(team, number);
+        }
+
+        if self.ball_throw.active && self.ball_throw.kind == ThrowKind::LineOut {
+            // The line of touch stays where it is while the ball flies
+            let (x, y) = (self.state.x, self.state.y);
+            self.update_ball_velocity();
+            self.state.x = x;
+            self.state.y = y;
+            self.contest_line_out();
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,60 @@
+use protocol::{Action, PlayerAction};
+
+use crate::game::game_state::GameState;
+
+impl GameState {
+
+    pub fn maul(&mut self, actions: Vec<PlayerAction>) {
+        self.time += 25;
+        let mut maul_h_pound = 0.0;
+        let mut maul_a_pound = 0.0;
+
+        for PlayerAction { team, number, action } in actions {
+            let number = number as i32;
+            match action {
+                Action::Run { direction } => {
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    self.run(team, number, direction, true);
+                },
+                Action::Walk { direction } => {
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    self.run(team, number, direction, false);
+                },
+                Action::Pass { direction } => {
+                    // The ball comes out of the maul
+                    if self.release_maul(team, number) {
+                        self.pass(team, number, direction);
+                    }
+                },
+                Action::Kick { direction, high } => {
+                    if self.release_maul(team, number) {
+                        self.kick(team, number, direction, high);
+                    }
+                },
+                Action::Stay => {
+                    // Players bound on the maul push with their weight
+                    if let Some(pound) = self.maul_pound(team, number) {
+                        if team == 'H' {
+                            maul_h_pound += pound;
+                        } else {
+                            maul_a_pound += pound;
+                        }
+                    }
+                },
+                _ => {
+                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                    println!("Unknown action in maul: {}", action.serialize());
+                },
+            }
+        }
+
+        if self.state.name == "maul" {
+            self.update_maul(maul_h_pound, maul_a_pound);
+        }
+        self.update_advantage();
+    }
+}
@@ -1,7 +1,11 @@
+pub mod dispatch;
 pub mod play;
 pub mod scrum;
 pub mod ruck;
+pub mod maul;
 pub mod line_out;
 pub mod set_penalty;
 pub mod set_transformation;
-pub mod set_line_out;
\ No newline at end of file
+pub mod set_line_out;
+pub mod set_not_straight;
+pub mod set_free_kick;
\ No newline at end of file
@@ -1,76 +1,80 @@
+use protocol::{Action, PlayerAction};
+
 use crate::game::game_state::GameState;
 
 impl GameState {
 
-    pub fn play(&mut self, input: String) {
-        let mut update = false;
+    pub fn play(&mut self, actions: Vec<PlayerAction>) {
         self.time += 25;
+        // Only a kick taken from a restart opens play, not a restart set up during this tick
+        let restart = self.state.name.clone();
+        let mut mark_calls = Vec::new();
 
-        let actions: Vec<&str> = input.split('\n').collect();
-        for action in actions {
-            if let Some((player, action)) = action.split_once(':') {
-                if update {
-                    return;
-                }
-                let tmp = action.chars().nth(0).unwrap_or('\0');
-                match tmp {
-                    'R' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let direction = action[1..].parse().unwrap_or(0.0);
-                        // println!("Player {} {} runs {}", team, number, direction);
-                        self.run(team, number, direction, true);
-                    },
-                    'W' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let direction = action[1..].parse().unwrap_or(0.0);
-                        // println!("Player {} {} walk {}", team, number, direction);
-                        self.run(team, number, direction, false);
-                    },
-                    'T' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        self.tackle(team, number);
-                    },
-                    'G' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        self.put_try(team, number);
-                    },
-                    'K' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let tmp: Vec<&str> = action[1..].split('/').collect();
-                        let direction = tmp[0].parse().unwrap_or(0.0);
-                        let high = tmp[1].parse().unwrap_or(0.0);
-                        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                        println!("Player {} {} Kick in way {} at {}", team, number, direction, high);
-                        self.kick(team, number, direction, high);
-                    },
-                    'P' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let tmp: Vec<&str> = action[1..].split('/').collect();
-                        let direction = tmp[0].parse().unwrap_or(0.0);
-                        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                        println!("Player {} {} Pass in way {}", team, number, direction);
-                        update = self.pass(team, number, direction);
-                    },
-                    'S' => continue,
-                    _ => {
-                        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                        print!("Unknown action: {}\n", action);
-                    },
-                }
+        for PlayerAction { team, number, action } in actions {
+            let number = number as i32;
+            match action {
+                Action::Run { direction } => {
+                    // println!("Player {} {} runs {}", team, number, direction);
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    self.run(team, number, direction, true);
+                },
+                Action::Walk { direction } => {
+                    // println!("Player {} {} walk {}", team, number, direction);
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    self.run(team, number, direction, false);
+                },
+                Action::Tackle => {
+                    if self.check_offside(team, number, None) {
+                        return;
+                    }
+                    self.tackle(team, number);
+                },
+                Action::Try => {
+                    self.put_try(team, number);
+                },
+                Action::Kick { direction, high } => {
+                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                    println!("Player {} {} Kick in way {} at {}", team, number, direction, high);
+                    self.kick(team, number, direction, high);
+                },
+                Action::Pass { direction } => {
+                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                    println!("Player {} {} Pass in way {}", team, number, direction);
+                    self.pass(team, number, direction);
+                },
+                Action::Mark => {
+                    if self.call_mark(team, number as usize) {
+                        return;
+                    }
+                    // Called under the ball, it counts if the player catches it in this tick
+                    mark_calls.push((team, number as usize));
+                },
+                Action::Stay => continue,
+                _ => {
+                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                    println!("Unknown action: {}", action.serialize());
+                },
             }
         }
 
         self.check_ball_position();
         self.update_ball_velocity();
+        let kicker = self.kick_on_the_full();
         self.update_ball_carrie();
+        self.record_fair_catch(kicker);
+        for (team, number) in mark_calls {
+            if self.call_mark(team, number) {
+                break;
+            }
+        }
+        self.update_advantage();
+        self.update_offside();
 
-        if self.state.name == "start" || self.state.name == "restart" {
+        if self.state.name == restart && (restart == "start" || restart == "restart" || restart == "drop-out") {
             self.state.name = "play".to_string();
         }
     }
@@ -1,51 +1,48 @@
+use protocol::{Action, PlayerAction};
+
 use crate::game::game_state::GameState;
 
 impl GameState {
 
-    pub fn ruck(&mut self, input: String) {
+    pub fn ruck(&mut self, actions: Vec<PlayerAction>) {
         self.time += 25;
-        let mut is_offside = false;
 
-        let actions: Vec<&str> = input.split('\n').collect();
-        for action in actions {
-            if let Some((player, action)) = action.split_once(':') {
-                let tmp = action.chars().nth(0).unwrap_or('\0');
-                match tmp {
-                    'R' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let direction = action[1..].parse().unwrap_or(0.0);
-                        // println!("Player {} {} runs {}", team, number, direction);
-                        self.run_ruck(team, number, direction, true);
-                    },
-                    'W' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let direction = action[1..].parse().unwrap_or(0.0);
-                        // println!("Player {} {} walk {}", team, number, direction);
-                        self.run_ruck(team, number, direction, false);
-                    },
-                    'T' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        is_offside = self.try_catch_ball_in_ruck(team, number);
-                    },
-                    'S' => continue,
-                    _ => {
-                        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                        print!("Unknown action in ruck: {}\n", action);
-                    },
-                }
+        for PlayerAction { team, number, action } in actions {
+            let number = number as i32;
+            match action {
+                Action::Run { direction } => {
+                    // println!("Player {} {} runs {}", team, number, direction);
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    self.run_ruck(team, number, direction, true);
+                },
+                Action::Walk { direction } => {
+                    // println!("Player {} {} walk {}", team, number, direction);
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    self.run_ruck(team, number, direction, false);
+                },
+                Action::Take => {
+                    if self.check_offside(team, number, None) {
+                        return;
+                    }
+                    self.try_catch_ball_in_ruck(team, number);
+                },
+                Action::Stay => continue,
+                _ => {
+                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                    println!("Unknown action in ruck: {}", action.serialize());
+                },
             }
         }
 
-        if is_offside {
-            self.setup_penalty(self.state.team, self.state.x, self.state.y);
-        }
-
-        if self.check_tackler() {
-            self.setup_penalty(if self.state.team == 'H' { 'A' } else { 'H' } , self.state.x, self.state.y);
+        if let Some(team_fouled) = self.check_tackler()
+            && self.foul_play(team_fouled, self.state.x, self.state.y) {
+            return;
         }
-        self.check_scrap();
+        self.update_ruck();
+        self.update_advantage();
     }
 }
\ No newline at end of file
@@ -1,9 +1,11 @@
+use protocol::{Action, PlayerAction};
+
 use crate::game::game_state::GameState;
 use crate::game::constants::*;
 
 impl GameState {
 
-    pub fn scrum(&mut self, input: String) {
+    pub fn scrum(&mut self, actions: Vec<PlayerAction>) {
         self.time += 25;
         let mut scrum_h_pound = 0.0;
         let mut scrum_a_pound = 0.0;
@@ -11,104 +13,72 @@ impl GameState {
         if self.state.size != SCRUM_SIZE {
             self.state.size = SCRUM_SIZE;
         }
+        if self.call_scrum() {
+            return;
+        }
 
-        let actions: Vec<&str> = input.split('\n').collect();
-        for action in actions {
-            if let Some((player, action)) = action.split_once(':') {
-                let tmp = action.chars().nth(0).unwrap_or('\0');
-                match tmp {
-                    'R' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let direction = action[1..].parse().unwrap_or(0.0);
-                        // println!("Player {} {} runs {}", team, number, direction);
-                        let players = if team == 'H' {
-                            &mut self.home_team.players
-                        } else {
-                            &mut self.away_team.players
-                        };
-                        let player_index = players.iter()
-                        .position(|p| p.number == number as usize)
-                        .unwrap_or(15);
-                        if player_index >= 7 && player_index < 15 {
-                            self.run(team, number, direction, true);
-                        } else  {
-                            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                            print!("Player {} {} cannot run during scrum\n", team, number);
-                        }
-                    },
-                    'W' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let direction = action[1..].parse().unwrap_or(0.0);
-                        // println!("Player {} {} walk {}", team, number, direction);
-                        let players = if team == 'H' {
-                            &mut self.home_team.players
-                        } else {
-                            &mut self.away_team.players
-                        };
-                        let player_index = players.iter()
-                        .position(|p| p.number == number as usize)
-                        .unwrap_or(15);
-                        if player_index >= 7 && player_index < 15 {
-                            self.run(team, number, direction, false);
-                        } else  {
-                            print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                            print!("Player {} {} cannot walk during scrum\n", team, number);
-                        }
-                    },
-                    'T' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        let players = if team == 'H' {
-                            &mut self.home_team.players
-                        } else {
-                            &mut self.away_team.players
-                        };
-                        let player_index = players.iter()
-                        .position(|p| p.number == number as usize)
-                        .unwrap_or(15);
-                        if player_index >= 9 && player_index < 15 {
+        for PlayerAction { team, number, action } in actions {
+            let number = number as i32;
+            match action {
+                Action::Run { direction } => {
+                    // println!("Player {} {} runs {}", team, number, direction);
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    // The number 8 and the backs are free, the front five and flankers stay bound
+                    if (8..=15).contains(&self.scrum_position(team, number)) {
+                        self.run(team, number, direction, true);
+                    } else  {
+                        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                        print!("Player {} {} cannot run during scrum\n", team, number);
+                    }
+                },
+                Action::Walk { direction } => {
+                    // println!("Player {} {} walk {}", team, number, direction);
+                    if self.check_offside(team, number, Some(direction)) {
+                        return;
+                    }
+                    if (8..=15).contains(&self.scrum_position(team, number)) {
+                        self.run(team, number, direction, false);
+                    } else  {
+                        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                        print!("Player {} {} cannot walk during scrum\n", team, number);
+                    }
+                },
+                Action::Take => {
+                    match self.scrum_position(team, number) {
+                        2 => self.hook_ball(team, number),
+                        10..=15 => {
                             print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
                             print!("Player {} {} is not in scrum\n", team, number);
-                        } else if player_index <= 6 {
+                        },
+                        1..=7 => {
                             print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
                             print!("Player {} {} add to push in scrum\n", team, number);
-                        } else if player_index == 7 || player_index == 8 {
-                            self.try_catch_ball_in_scrum(team, number);
+                        },
+                        8 | 9 => self.try_catch_ball_in_scrum(team, number),
+                        _ => continue,
+                    }
+                },
+                Action::Stay => {
+                    // println!("Player {} {} stop or scrum", team, number);
+                    if let Some(pound) = self.scrum_pound(team, number) {
+                        if team == 'H' {
+                            scrum_h_pound += pound;
                         } else {
-                            continue;
+                            scrum_a_pound += pound;
                         }
-                    },
-                    'S' => {
-                        let team = player.chars().next().unwrap();
-                        let number = player[1..].parse().unwrap_or(0);
-                        // println!("Player {} {} stop or scrum", team, number);
-                        let players = if team == 'H' {
-                            &mut self.home_team.players
-                        } else {
-                            &mut self.away_team.players
-                        };
-                        let player_index = players.iter()
-                        .position(|p| p.number == number as usize)
-                        .unwrap_or(15);
-                        if player_index <= 7 {
-                            if team == 'H' {
-                                scrum_h_pound += players[player_index].pound;
-                            } else {
-                                scrum_a_pound += players[player_index].pound;
-                            }
-                        } else  {
-                            continue;
-                        }
-                    },
-                    _ => {
-                        print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                        print!("Unknown action in scrum: {}\n", action);
-                    },
-                }
+                    }
+                },
+                _ => {
+                    print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                    println!("Unknown action in scrum: {}", action.serialize());
+                },
             }
         }
+        if self.update_scrum(scrum_h_pound, scrum_a_pound) {
+            return;
+        }
         self.update_ball_position_scrum(scrum_h_pound, scrum_a_pound);
         self.check_ball_out_of_scrum();
     }
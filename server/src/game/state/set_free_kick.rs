@@ -0,0 +1,21 @@
+use protocol::Command;
+
+use crate::game::game_state::GameState;
+
+impl GameState {
+
+    pub fn set_free_kick(&mut self, command: Command) {
+        match command {
+            Command::FreeKick { number, direction, high } => {
+                self.setup_free_kick(number, direction, high);
+            },
+            Command::Scrum => {
+                self.setup_scrum(self.state.team, self.state.x, self.state.y);
+            },
+            _ => {
+                print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                println!("Unknown action in free kick: {}", command.serialize());
+            }
+        }
+    }
+}
@@ -1,21 +1,22 @@
+use protocol::Command;
+
 use crate::game::game_state::GameState;
 
 impl GameState {
 
-    pub fn set_line_out(&mut self, input: String) {
-
-        let info: Vec<&str> = input.split('\n').collect();
-        let action: char = info[1].chars().nth(0).unwrap_or('\0');
-        match action {
-            'O' => {
-                let action: Vec<&str> = info[1][1..].split('/').collect();
-                let number: usize = action[1].parse().unwrap_or(0);
-
-                self.setup_line_out(number, action[2].to_string().split('-').collect(), action[3].to_string().split('-').collect());
+    pub fn set_line_out(&mut self, command: Command) {
+        // The clock runs while the team decides how to restart
+        self.time += 25;
+        match command {
+            Command::LineOut { number, home_line, away_line } => {
+                self.setup_line_out(number, home_line, away_line);
+            },
+            Command::QuickThrow { number, x, angle } => {
+                self.quick_throw_in(number, x, angle);
             },
             _ => {
                 print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                print!("Unknown action in transformation: {}\n", action);
+                println!("Unknown action in line out: {}", command.serialize());
             }
         }
     }
@@ -0,0 +1,30 @@
+use protocol::Command;
+
+use crate::game::game_state::GameState;
+use crate::game::constants::*;
+
+impl GameState {
+
+    pub fn set_not_straight(&mut self, command: Command) {
+        match command {
+            Command::Scrum => {
+                // The scrum is set 15 meters in from touch
+                let y = if self.state.y == 0.5 { 0.5 + NOT_STRAIGHT_SCRUM } else { self.state.y - NOT_STRAIGHT_SCRUM };
+                for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+                    player.ball_pos = false;
+                }
+                self.setup_scrum(self.state.team, self.state.x, y);
+            },
+            Command::LineOut { number, home_line, away_line } => {
+                for player in self.home_team.players.iter_mut().chain(self.away_team.players.iter_mut()) {
+                    player.ball_pos = false;
+                }
+                self.setup_line_out(number, home_line, away_line);
+            },
+            _ => {
+                print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
+                println!("Unknown action after a throw not straight: {}", command.serialize());
+            }
+        }
+    }
+}
@@ -1,33 +1,72 @@
+use protocol::Command;
+
 use crate::game::game_state::GameState;
 
 impl GameState {
 
-    pub fn set_penalty(&mut self, input: String) {
-
-        let info: Vec<&str> = input.split('\n').collect();
-        let action: char = info[1].chars().nth(0).unwrap_or('\0');
-        match action {
-            'P' => {
-                let action: Vec<&str> = info[1][1..].split('/').collect();
-                let number: usize = action[1].parse().unwrap_or(0);
-                let direction: f32 = action[2].parse().unwrap_or(0.0);
-                let high: f32 = action[3].parse().unwrap_or(0.0);
+    pub fn set_penalty(&mut self, command: Command) {
+        match command {
+            Command::PenaltyKick { number, direction, high } => {
                 self.setup_penalty_kick(number, direction, high);
             },
-            'K' => {
-                let action: Vec<&str> = info[1][1..].split('/').collect();
-                let number: usize = action[1].parse().unwrap_or(0);
-                let direction: f32 = action[2].parse().unwrap_or(0.0);
-                let high: f32 = action[3].parse().unwrap_or(0.0);
+            Command::FreeKick { number, direction, high } => {
                 self.setup_free_kick(number, direction, high);
             },
-            'S' => {
+            Command::TouchKick { number, direction, high } => {
+                self.setup_touch_kick(number, direction, high);
+            },
+            Command::QuickTap { number } => {
+                self.setup_quick_tap(number);
+            },
+            Command::Scrum => {
                 self.setup_scrum(self.state.team, self.state.x, self.state.y);
             },
             _ => {
                 print!("{}|{:.2}|{}|", self.addr, (self.time as f32)/100.0, self.state.name);
-                print!("Unknown action in penalty: {}\n", action);
+                println!("Unknown action in penalty: {}", command.serialize());
+            }
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use protocol::{ClientMessage, Command};
+
+    use crate::game::game_state::GameState;
+    use crate::game::constants::*;
+
+    // Penalty to home at x = 60, home attacks towards increasing x
+    fn penalty(command: Command) -> GameState {
+        let mut game = GameState::seeded(2, "set-penalty H 60 30");
+        game.dispatch(ClientMessage::Command { state: "set-penalty".to_string(), command });
+        game
+    }
+
+    #[test]
+    fn quick_tap_plays_on_with_the_opponents_back_10_m() {
+        let game = penalty(Command::QuickTap { number: 9 });
+        assert_eq!(game.state.name, "play");
+        assert!(game.home_team.players.iter().any(|p| p.number == 9 && p.ball_pos));
+        assert!(game.away_team.players.iter().all(|p| p.x >= game.state.x + PENALTY_RETIRE));
+    }
+
+    #[test]
+    fn scrum_option_puts_the_ball_in_for_the_team_awarded() {
+        let game = penalty(Command::Scrum);
+        assert_eq!(game.state.name, "scrum");
+        assert_eq!(game.state.team, 'H');
+    }
+
+    #[test]
+    fn kick_to_touch_keeps_the_throw() {
+        let mut game = penalty(Command::TouchKick { number: 10, direction: 315.0, high: 30.0 });
+        for _ in 0..40 {
+            if game.state.name != "touch-kick" {
+                break;
             }
+            game.dispatch(ClientMessage::Actions { state: game.state.name.clone(), actions: vec![] });
         }
+        assert_eq!(game.state.name, "set-line_out");
+        assert_eq!(game.state.team, 'H');
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,5 @@
 use minifb::{Window, WindowOptions};
-use crate::{game::constants::SCRUM_SIZE, gui::drawable::Drawable};
+use crate::{game::constants::{LINE_OUT_OFFSIDE, SCRUM_OFFSIDE, SCRUM_SIZE}, gui::drawable::Drawable};
 
 const GROUND_COLOR: u32 = 0xFF66D575;
 const GROUND_LINE_COLOR: u32 = 0xFFCDF4D3;
@@ -18,11 +18,15 @@ const SCRUM_LINE: u32 = 0xFF9267EE;
 const RUCK: u32 = 0xFF00DFFF;
 const RUCK_LINE: u32 = 0xFF20C2BD;
 const OFFSIDE_LINE: u32 = 0xFFE63319;
+const MAUL: u32 = 0xFFFFCF00;
+const MAUL_LINE: u32 = 0xFFBE9D09;
 const LINE_OUT_HALL_WIDTH: f32 = 3.0;
 const LINE_OUT_HALL_HEIGHT: f32 = 15.5;
 const LINE_OUT_LINE_FIVE: f32 = 5.5;
 const LINE_OUT: u32 = 0xFFFFAA11;
 const LINE_OUT_BORDER: u32 = 0xFFFF7F00;
+const YELLOW_CARD: u32 = 0xFFFFE600;
+const RED_CARD: u32 = 0xFFE00000;
 
 
 const FONT_3X5: [[&str; 5]; 10] = [
@@ -46,6 +50,7 @@ pub struct Display {
     size_x: usize,
     size_y: usize,
     try_size: usize,
+    line_22: usize,
     time: u64,
     is_initialized: bool,
 }
@@ -65,7 +70,7 @@ impl Display {
         let time = 0;
         let is_initialized = false;
 
-        Self { window, buffer, width, height, size_x:size, size_y:height, try_size, time, is_initialized }
+        Self { window, buffer, width, height, size_x:size, size_y:height, try_size, line_22: 22, time, is_initialized }
     }
 
     pub fn initialize(&mut self, field: String, pixel_per_cell: usize) {
@@ -83,6 +88,11 @@ impl Display {
             .and_then(|s| s.split('=').nth(1))
             .and_then(|v| v.parse().ok())
             .unwrap_or(10) * pixel_per_cell;
+        self.line_22 = field_parts.iter()
+            .find(|s| s.starts_with("l22="))
+            .and_then(|s| s.split('=').nth(1))
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(22);
 
         self.size_x = self.width + 2 * self.try_size + 2 * pixel_per_cell;
         self.size_y = self.height + 2 * pixel_per_cell;
@@ -130,11 +140,11 @@ impl Display {
                     OFFSIDE_LINE
                 );
                 self.draw_line(
-                    ((drawable.state.pos.x - SCRUM_SIZE - 5.0) * pixel_per_cell as f32) as usize,
+                    ((drawable.state.pos.x - SCRUM_SIZE - SCRUM_OFFSIDE) * pixel_per_cell as f32) as usize,
                     OFFSIDE_LINE
                 );
                 self.draw_line(
-                    ((drawable.state.pos.x + SCRUM_SIZE + 5.0) * pixel_per_cell as f32) as usize,
+                    ((drawable.state.pos.x + SCRUM_SIZE + SCRUM_OFFSIDE) * pixel_per_cell as f32) as usize,
                     OFFSIDE_LINE
                 );
             },
@@ -155,13 +165,30 @@ impl Display {
                     OFFSIDE_LINE
                 );
             },
+            "maul" => {
+                self.draw_circle(
+                    (drawable.state.pos.x * pixel_per_cell as f32) as usize,
+                    (drawable.state.pos.y * pixel_per_cell as f32) as usize,
+                    (drawable.state.size * pixel_per_cell as f32) as usize,
+                    MAUL,
+                    MAUL_LINE
+                );
+                self.draw_line(
+                    ((drawable.state.pos.x + drawable.state.size) * pixel_per_cell as f32) as usize,
+                    OFFSIDE_LINE
+                );
+                self.draw_line(
+                    ((drawable.state.pos.x - drawable.state.size) * pixel_per_cell as f32) as usize,
+                    OFFSIDE_LINE
+                );
+            },
             "line_out" => {
                 self.draw_line(
-                    ((drawable.state.pos.x + 5.0) * pixel_per_cell as f32 - 1.0) as usize,
+                    ((drawable.state.pos.x + LINE_OUT_OFFSIDE) * pixel_per_cell as f32 - 1.0) as usize,
                     OFFSIDE_LINE
                 );
                 self.draw_line(
-                    ((drawable.state.pos.x - 5.0) * pixel_per_cell as f32) as usize,
+                    ((drawable.state.pos.x - LINE_OUT_OFFSIDE) * pixel_per_cell as f32) as usize,
                     OFFSIDE_LINE
                 );
                 self.draw_rectangle((drawable.state.pos.x * pixel_per_cell as f32) as usize,
@@ -260,6 +287,7 @@ impl Display {
 
         // Draw score
         self.draw_score(drawable.home_score, drawable.away_score, pixel_per_cell);
+        self.draw_cards(&drawable.home_cards, &drawable.away_cards, pixel_per_cell);
 
         // Update the window
         self.window
@@ -323,6 +351,9 @@ impl Display {
         //     }
         // }
 
+        // 22 + 1 for out
+        let line_22 = (self.line_22 + 1) * pixel_per_cell;
+
         // RAW
         for j in 0..self.size_x {
             // Draw out lines
@@ -334,16 +365,16 @@ impl Display {
             let center = (pixel_per_cell * 5) / 2;
             if (j > self.try_size + pixel_per_cell * 6) &&
             (j < self.try_size + pixel_per_cell * 11) ||
-            (j > self.try_size + pixel_per_cell * 23 - center) &&
-            (j < self.try_size + pixel_per_cell * 23 + center) ||
+            (j > self.try_size + line_22 - center) &&
+            (j < self.try_size + line_22 + center) ||
             (j > self.try_size + pixel_per_cell * 41 - center) &&
             (j < self.try_size + pixel_per_cell * 41 + center) ||
             (j > self.size_x / 2 - center) &&
             (j < self.size_x / 2 + center) ||
             (j > self.size_x - self.try_size - pixel_per_cell * 41 - center) &&
             (j < self.size_x - self.try_size - pixel_per_cell * 41 + center) ||
-            (j > self.size_x - self.try_size - pixel_per_cell * 23 - center) &&
-            (j < self.size_x - self.try_size - pixel_per_cell * 23 + center) ||
+            (j > self.size_x - self.try_size - line_22 - center) &&
+            (j < self.size_x - self.try_size - line_22 + center) ||
             (j > self.size_x - self.try_size - pixel_per_cell * 11) &&
             (j < self.size_x - self.try_size - pixel_per_cell * 6) {
                 // Draw lines of 5 + 1 for out
@@ -368,8 +399,8 @@ impl Display {
                 self.buffer[j * self.size_x + self.try_size + pixel_per_cell] = GROUND_LINE_COLOR;
                 self.buffer[j * self.size_x + self.size_x - self.try_size - pixel_per_cell] = GROUND_LINE_COLOR;
                 // Draw lines of 22
-                self.buffer[j * self.size_x + self.try_size + (23 * pixel_per_cell)] = GROUND_LINE_COLOR;
-                self.buffer[j * self.size_x + self.size_x - self.try_size - (23 * pixel_per_cell)] = GROUND_LINE_COLOR;
+                self.buffer[j * self.size_x + self.try_size + line_22] = GROUND_LINE_COLOR;
+                self.buffer[j * self.size_x + self.size_x - self.try_size - line_22] = GROUND_LINE_COLOR;
             }
             // Draw hashed lines of 5 + 1 for out
             let center = (pixel_per_cell * 5) / 2;
@@ -575,6 +606,20 @@ impl Display {
         }
     }
 
+    // One small square per card, under the score of each team
+    fn draw_cards(&mut self, home_cards: &[char], away_cards: &[char], pixel_per_cell: usize) {
+        let size = pixel_per_cell / 2 + 1;
+        let y = pixel_per_cell * 9 + pixel_per_cell / 2;
+        for (i, card) in home_cards.iter().enumerate() {
+            let color = if *card == 'R' { RED_CARD } else { YELLOW_CARD };
+            self.draw_square(pixel_per_cell + i * (size + 2), y, size, color);
+        }
+        for (i, card) in away_cards.iter().enumerate() {
+            let color = if *card == 'R' { RED_CARD } else { YELLOW_CARD };
+            self.draw_square(pixel_per_cell * 6 + i * (size + 2), y, size, color);
+        }
+    }
+
     fn draw_square(&mut self, x: usize, y: usize, size: usize, color: u32) {
         for dy in 0..size {
             for dx in 0..size {
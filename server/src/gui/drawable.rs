@@ -19,6 +19,8 @@ pub struct Drawable {
     pub away_players: Vec<DrawPlayer>,
     pub home_score: usize,
     pub away_score: usize,
+    pub home_cards: Vec<char>, // 'Y' for each player in the sin bin, 'R' for each sent off
+    pub away_cards: Vec<char>,
     pub time: u64,
 }
 
@@ -35,6 +37,8 @@ impl Drawable {
             away_players: Vec::new(),
             home_score: 0,
             away_score: 0,
+            home_cards: Vec::new(),
+            away_cards: Vec::new(),
             time: 0,
         }
     }
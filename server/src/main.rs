@@ -2,8 +2,10 @@ use std::env;
 use dotenv::dotenv;
 use std::collections::HashMap;
 use std::thread;
-use std::net::{SocketAddr, TcpListener};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
 use std::sync::mpsc::{channel, Sender, Receiver};
+use std::time::Duration;
 
 mod gui;
 use gui::display::Display;
@@ -14,6 +16,8 @@ mod game;
 mod network;
 use network::handler::handle_client;
 use network::event::ClientEvent;
+use network::lobby::{Lobby, MatchId, SharedLobby};
+use network::session::{Settings, TickMode};
 
 fn main() {
     // Load environment variables from .env file
@@ -31,8 +35,21 @@ fn main() {
     let display_enable: bool = env::var("DISPLAY").unwrap_or("false".to_string()).to_lowercase() == "true";
     let run_time: bool = env::var("RUN_TIME").unwrap_or("false".to_string()).to_lowercase() == "true";
     let pixel_per_cell: usize = env::var("PIXEL_SIZE").unwrap_or("0".to_string()).parse().unwrap();
-    let mut displays: HashMap<SocketAddr, Display> = HashMap::new();
-    let mut drawables: HashMap<SocketAddr, Drawable> = HashMap::new();
+    // Lock-step plays a tick once every client answered, real-time plays one per deadline
+    let tick_mode = if env::var("TICK_MODE").unwrap_or("lockstep".to_string()).to_lowercase() == "realtime" {
+        TickMode::RealTime
+    } else {
+        TickMode::LockStep
+    };
+    // Time given to the clients of a match to answer, in milliseconds
+    let tick_deadline: u64 = env::var("TICK_DEADLINE").unwrap_or("1000".to_string()).parse().unwrap();
+    // Same seed for every match, to replay one
+    let seed: Option<u64> = env::var("SEED").ok().and_then(|seed| seed.parse().ok());
+    let settings = Settings { display_enable, run_time, tick_mode, tick_deadline: Duration::from_millis(tick_deadline), seed };
+    // Matches created, waiting for players or in progress
+    let lobby: SharedLobby = Arc::new(Mutex::new(Lobby::default()));
+    let mut displays: HashMap<MatchId, Display> = HashMap::new();
+    let mut drawables: HashMap<MatchId, Drawable> = HashMap::new();
 
     // Start TCP listener
     let listener = TcpListener::bind(&address).expect("Failed to bind server address");
@@ -52,22 +69,10 @@ fn main() {
             Ok((stream, _addr)) => {
                 println!("New client connected {}.", stream.peer_addr().unwrap());
 
-                // Initialize a new client state
-                let client_id = stream.peer_addr().unwrap();
-
-                if display_enable {
-                    let field_width: usize = env::var("FIELD_MAX_WIDTH").unwrap_or("100".to_string()).parse().unwrap();
-                    let field_height: usize = env::var("FIELD_MAX_HEIGHT").unwrap_or("70".to_string()).parse().unwrap();
-                    let try_size: usize = env::var("TRY_MIN_SIZE").unwrap_or("10".to_string()).parse().unwrap();
-                    let display = Display::new((field_width + 2) * pixel_per_cell, (field_height + 2) * pixel_per_cell,try_size * pixel_per_cell);
-
-                    displays.insert(client_id, display);
-                }
                 let tx_clone = tx.clone();
-                let display_enable_clone = display_enable.clone();
-                let run_time_clone = run_time.clone();
+                let lobby_clone = lobby.clone();
                 thread::spawn(move || {
-                    handle_client(stream, display_enable_clone, run_time_clone, tx_clone);
+                    handle_client(stream, lobby_clone, settings, tx_clone);
                 });
             }
             Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
@@ -81,23 +86,28 @@ fn main() {
         // Catch clients event (from handle_client via tx)
         while let Ok(event) = rx.try_recv() {
             match event {
-                ClientEvent::Disconnected(addr) => {
-                    if let Some(mut display) = displays.remove(&addr) {
+                ClientEvent::Finished(id) => {
+                    drawables.remove(&id);
+                    if let Some(mut display) = displays.remove(&id) {
                         display.close();
-                        println!("Cleaned up {} window.", addr);
+                        println!("Cleaned up match {} window.", id);
                     }
                 }
-                ClientEvent::Initialized { addr, field, drawable} => {
+                ClientEvent::Initialized { id, field, drawable} => {
                     if display_enable {
-                        if let Some(display) = displays.get_mut(&addr) {
-                            display.initialize(field, pixel_per_cell);
-                            drawables.insert(addr, drawable);
-                        }
+                        // One window per match, opened at kick off
+                        let field_width: usize = env::var("FIELD_MAX_WIDTH").unwrap_or("100".to_string()).parse().unwrap();
+                        let field_height: usize = env::var("FIELD_MAX_HEIGHT").unwrap_or("70".to_string()).parse().unwrap();
+                        let try_size: usize = env::var("TRY_MIN_SIZE").unwrap_or("10".to_string()).parse().unwrap();
+                        let mut display = Display::new((field_width + 2) * pixel_per_cell, (field_height + 2) * pixel_per_cell,try_size * pixel_per_cell);
+                        display.initialize(field, pixel_per_cell);
+                        displays.insert(id, display);
+                        drawables.insert(id, drawable);
                     }
                 }
-                ClientEvent::DisplayUpdate { addr, drawable } => {
+                ClientEvent::DisplayUpdate { id, drawable } => {
                     if display_enable {
-                        drawables.insert(addr, drawable);
+                        drawables.insert(id, drawable);
                     }
                 }
             }
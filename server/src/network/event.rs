@@ -1,15 +1,15 @@
-use std::net::SocketAddr;
 use crate::gui::drawable::Drawable;
+use crate::network::lobby::MatchId;
 
 pub enum ClientEvent {
-    Disconnected(SocketAddr),
+    Finished(MatchId),
     Initialized {
-        addr: SocketAddr,
+        id: MatchId,
         field: String,
         drawable: Drawable,
     },
     DisplayUpdate {
-        addr: SocketAddr,
+        id: MatchId,
         drawable: Drawable,
     },
-}
\ No newline at end of file
+}
@@ -3,134 +3,127 @@ use std::sync::mpsc::Sender;
 use std::net::{SocketAddr, TcpStream};
 use std::io::ErrorKind;
 
+use protocol::{frame, negotiate_version, ClientMessage, FrameReader, ServerMessage};
+
 use crate::game::game_state::GameState;
 use crate::network::event::ClientEvent;
+use crate::network::lobby::SharedLobby;
+use crate::network::session::{Seat, Session, Settings};
 
-/// Handles communication with a single client.
-pub fn handle_client(mut stream: TcpStream, display_enable: bool, run_time: bool, tx: Sender<ClientEvent>) {
-    let mut buffer = [0; 2500];
-    let mut status= 0;
+/// Handles a new client in the lobby until it creates a match with `init` or takes a seat with `join`.
+pub fn handle_client(mut stream: TcpStream, lobby: SharedLobby, settings: Settings, tx: Sender<ClientEvent>) {
+    let mut reader = FrameReader::new();
     let addr = stream.peer_addr().unwrap();
-    // Initialize the client environment
-    let mut client = GameState::new();
+    let _ = stream.set_nonblocking(true);
 
     loop {
-        match stream.read(&mut buffer) {
-            Ok(0) => {
-                println!("Client {} disconnected.", addr);
-                if display_enable {
-                    let _ = tx.send(ClientEvent::Disconnected(addr));
-                }
-                break;
+        let Some(message) = read_lobby(&mut stream, &mut reader, addr, &lobby) else {
+            return;
+        };
+        match message {
+            ClientMessage::Init { version, field, home_players, away_players, state, side } => {
+                println!("Initialization message received from {}", addr);
+                let Some(version) = agree_version(&mut stream, addr, version) else {
+                    return;
+                };
+
+                // Always a new match with the squads of this client, the other side takes a seat with `join`
+                let (id, joiner) = lobby.lock().unwrap().create(addr, side);
+
+                // Initialize the match environment
+                let mut game = GameState::new();
+                // A seed in the field settings of the match wins over the server one
+                game.reseed(settings.seed.unwrap_or_else(rand::random));
+                game.initialize(field.clone(), home_players, away_players, state, addr.to_string());
+                let seat = Seat::new(side, addr, version, stream, reader);
+                Session::new(id, game, field, seat, joiner).run(settings, &tx, &lobby);
+                return;
             }
-            Ok(n) => {
-
-                // INIT
-                if buffer.starts_with(b"init") && status == 0 {
-                    println!("Initialization message received from {}", addr);
-
-                    let tmp  = buffer.split(|&byte| byte == b'\n');
-                    let mut field: String = String::new();
-                    let mut state: String = String::new();
-                    let mut home_players: Vec<String> = Vec::new();
-                    let mut away_players: Vec<String> = Vec::new();
-
-                    for (index, part) in tmp.enumerate() {
-                        if index == 0 {
-                            // Skip the first part which is "init"
-                            continue;
-                        } else if index == 1 {
-                            // The second part is the field
-                            field = String::from_utf8_lossy(part).to_string();
-                        } else if index <= 24 {
-                            // The next 23 parts are home players
-                            home_players.push(std::str::from_utf8(part).expect("Invalid UTF-8 sequence").to_string());
-                        } else if index > 24 && index <= 47 {
-                            // The remaining parts are away players
-                            away_players.push(std::str::from_utf8(part).expect("Invalid UTF-8 sequence").to_string());
-                        } else if index == 48 {
-                            // The remaining parts are away players
-                            let cleaned = part.split(|&b| b == 0).next().unwrap();
-                            state = String::from_utf8_lossy(cleaned).to_string();
-                        } else {
-                            print!("Extra data received during initialization from {}: {:?}\n", addr, std::str::from_utf8(part).expect("Invalid UTF-8 sequence").to_string());
+            ClientMessage::Join { version, id, side } => {
+                let Some(version) = agree_version(&mut stream, addr, version) else {
+                    return;
+                };
+
+                let mut lobby_guard = lobby.lock().unwrap();
+                match lobby_guard.assign(id, side) {
+                    Ok(side) => {
+                        if !lobby_guard.seat(id, Seat::new(side, addr, version, stream, reader)) {
+                            println!("Match {} is over, client {} not seated", id, addr);
                         }
+                        return;
                     }
-
-                    client.initialize(field.clone(), home_players, away_players, state, addr.to_string());
-                    if display_enable {
-                        std::thread::sleep(std::time::Duration::from_millis(240));
-                        let _ = tx.send(ClientEvent::Initialized {
-                            addr,
-                            field,
-                            drawable: client.get_drawable(),
-                        });
-                    }
-
-                    status = 1; // Change state to indicate initialization is done
-                    let mut response = format!("{} {}\n", client.state.name, client.state.team);
-                    response.push_str(&format!("time:{}\n", &client.time.to_string()));
-                    response.push_str(client.positions().as_str());
-
-                    if let Err(e) = stream.write_all(response.as_bytes()) {
-                        println!("Failed to send player positions: {}", e);
-                        break;
+                    Err(reason) => {
+                        drop(lobby_guard);
+                        println!("Client {} cannot join: {}", addr, reason);
+                        if send(&mut stream, &ServerMessage::Error { reason }) {
+                            return;
+                        }
                     }
-                    buffer.fill(0);
-                    continue;
                 }
+            }
+            _ => {}
+        }
+    }
+}
 
-                // ACTION HANDLING
-
-                let handlers: &[(&[u8], fn(&mut GameState, String))] = &[
-                    (b"start", GameState::play),
-                    (b"play", GameState::play),
-                    (b"restart", GameState::play),
-                    (b"free-kick", GameState::play),
-                    (b"penalty-kick", GameState::play),
-                    (b"transformation-kick", GameState::play),
-                    (b"scrum", GameState::scrum),
-                    (b"ruck", GameState::ruck),
-                    (b"line_out", GameState::line_out),
-                    (b"set-penalty", GameState::set_penalty),
-                    (b"set-transformation", GameState::set_transformation),
-                    (b"set-line_out", GameState::set_line_out),
-                ];
+// Version both sides speak, the client is told and dropped when there is none
+fn agree_version(stream: &mut TcpStream, addr: SocketAddr, requested: u32) -> Option<u32> {
+    match negotiate_version(requested) {
+        Ok(version) => Some(version),
+        Err(e) => {
+            println!("Refused client {}: {}", addr, e);
+            let _ = send(stream, &ServerMessage::Error { reason: e.to_string() });
+            None
+        }
+    }
+}
 
-                if status == 1 {
-                    for (cmd, handler) in handlers {
-                        if buffer.starts_with(b"empty") {
-                            print!("{}|", addr);
-                            print!("The client receive an empty message\n");
-                            let mut response = format!("{} {}\n", client.state.name, client.state.team);
-                            response.push_str(&format!("time:{}\n", client.time));
-                            response.push_str(client.positions().as_str());
-                            if let Err(e) = stream.write_all(response.trim().as_bytes()) {
-                                println!("Failed to resend : {}", e);
-                                break;
+// Read until a message to create or join a match, answering `list` with the matches of the lobby
+fn read_lobby(stream: &mut TcpStream, reader: &mut FrameReader, addr: SocketAddr, lobby: &SharedLobby) -> Option<ClientMessage> {
+    let mut buffer = [0; 4096];
+    loop {
+        match stream.read(&mut buffer) {
+            Ok(0) => {
+                println!("Client {} disconnected.", addr);
+                return None;
+            }
+            Ok(n) => {
+                reader.push(&buffer[..n]);
+
+                // Several messages can arrive in one read, or one message over several reads
+                loop {
+                    let input = match reader.next_frame() {
+                        Ok(Some(input)) => input,
+                        Ok(None) => break,
+                        Err(e) => {
+                            println!("Corrupted stream from {}: {}", addr, e);
+                            let _ = send(stream, &ServerMessage::Error { reason: e.to_string() });
+                            return None;
+                        }
+                    };
+
+                    match ClientMessage::parse(&input) {
+                        Ok(message @ (ClientMessage::Init { .. } | ClientMessage::Join { .. })) => return Some(message),
+                        Ok(ClientMessage::List) => {
+                            let matches = lobby.lock().unwrap().list();
+                            if send(stream, &ServerMessage::Matches(matches)) {
+                                return None;
                             }
-                            buffer.fill(0);
-                            continue;
                         }
-                        if buffer.starts_with(cmd) {
-                            if handle_action(
-                                &buffer, n, addr, &mut client, &mut stream, &tx,
-                                display_enable, run_time,
-                                |c, i| handler(c, i),
-                            ) {
-                                break;
+                        Ok(_) => {
+                            println!("Unrecognized input from {}: {}", addr, input);
+                            if send(stream, &ServerMessage::Error { reason: "unexpected message".to_string() }) {
+                                return None;
+                            }
+                        }
+                        Err(e) => {
+                            println!("Invalid message from {}: {}", addr, e);
+                            if send(stream, &ServerMessage::Error { reason: e.to_string() }) {
+                                return None;
                             }
-                            buffer.fill(0);
-                            continue;
                         }
                     }
                 }
-
-                // Handle unrecognized input
-                if buffer[..n].iter().any(|&byte| byte != 0) {
-                    println!("Unrecognized input from {}: {}", addr, String::from_utf8_lossy(&buffer[..n]).to_string());
-                    buffer.fill(0);
-                }
             }
             Err(ref e) if e.kind() == ErrorKind::WouldBlock => {
                 // No data to read yet, it's fine, just wait a little
@@ -139,43 +132,13 @@ pub fn handle_client(mut stream: TcpStream, display_enable: bool, run_time: bool
             }
             Err(e) => {
                 println!("Failed to read from stream: {}", e);
-                break;
+                return None;
             }
         }
     }
 }
 
-fn handle_action<F>(
-    buffer: &[u8],
-    n: usize,
-    addr: SocketAddr,
-    client: &mut GameState,
-    stream: &mut TcpStream,
-    tx: &Sender<ClientEvent>,
-    display_enable: bool,
-    run_time: bool,
-    action: F,
-) -> bool
-where
-    F: FnOnce(&mut GameState, String),
-{
-    let input = String::from_utf8_lossy(&buffer[..n]).to_string();
-
-    action(client, input);
-
-    let mut response = format!("{} {}\n", client.state.name, client.state.team);
-    response.push_str(&format!("time:{}\n", client.time));
-    response.push_str(client.positions().as_str());
-
-    if display_enable {
-        if run_time {
-            std::thread::sleep(std::time::Duration::from_millis(200));
-        }
-        let _ = tx.send(ClientEvent::DisplayUpdate {
-            addr,
-            drawable: client.get_drawable(),
-        });
-    }
-
-    stream.write_all(response.trim().as_bytes()).is_err()
+/// Serialize and write a message, returns true when the stream is broken.
+fn send(stream: &mut TcpStream, message: &ServerMessage) -> bool {
+    stream.write_all(&frame::encode(&message.serialize())).is_err()
 }
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use protocol::{MatchInfo, BOTH_SIDES, SPECTATOR};
+
+use crate::network::session::Seat;
+
+pub type MatchId = u32;
+
+// Seats of a match as the lobby sees them, the session owns the connections
+struct MatchEntry {
+    creator: SocketAddr,
+    home: bool,
+    away: bool,
+    spectators: usize,
+    playing: bool,
+    joiner: Sender<Seat>,
+}
+
+impl MatchEntry {
+    fn taken(&self, side: char) -> bool {
+        if side == 'H' { self.home } else { self.away }
+    }
+}
+
+/// Matches open on the server, shared by every connection.
+#[derive(Default)]
+pub struct Lobby {
+    next_id: MatchId,
+    matches: BTreeMap<MatchId, MatchEntry>,
+}
+
+pub type SharedLobby = Arc<Mutex<Lobby>>;
+
+impl Lobby {
+    /// Register a match created by a client playing `side`, the seats joining it arrive on the receiver.
+    pub fn create(&mut self, creator: SocketAddr, side: char) -> (MatchId, Receiver<Seat>) {
+        self.next_id += 1;
+        let (joiner, seats) = channel();
+        self.matches.insert(self.next_id, MatchEntry {
+            creator,
+            home: side != 'A',
+            away: side != 'H',
+            spectators: 0,
+            playing: side == BOTH_SIDES,
+            joiner,
+        });
+        println!("Match {} created by {}", self.next_id, creator);
+        (self.next_id, seats)
+    }
+
+    /// Reserve a seat in match `id`: the side asked for, or home, away then spectator, whichever is free.
+    pub fn assign(&mut self, id: MatchId, side: Option<char>) -> Result<char, String> {
+        let entry = self.matches.get_mut(&id).ok_or_else(|| format!("no match {}", id))?;
+        let side = side.unwrap_or_else(|| ['H', 'A'].into_iter().find(|s| !entry.taken(*s)).unwrap_or(SPECTATOR));
+        match side {
+            SPECTATOR => entry.spectators += 1,
+            _ if entry.taken(side) => return Err(format!("team {} already taken in match {}", side, id)),
+            'H' => entry.home = true,
+            _ => entry.away = true,
+        }
+        entry.playing = entry.home && entry.away;
+        Ok(side)
+    }
+
+    /// Hand the connection of a reserved seat to the session of match `id`.
+    pub fn seat(&self, id: MatchId, seat: Seat) -> bool {
+        self.matches.get(&id).is_some_and(|entry| entry.joiner.send(seat).is_ok())
+    }
+
+    /// A spectator stopped watching match `id`.
+    pub fn leave(&mut self, id: MatchId) {
+        if let Some(entry) = self.matches.get_mut(&id) {
+            entry.spectators = entry.spectators.saturating_sub(1);
+        }
+    }
+
+    /// Forget a finished or abandoned match.
+    pub fn remove(&mut self, id: MatchId) {
+        if let Some(entry) = self.matches.remove(&id) {
+            println!("Match {} created by {} removed from the lobby", id, entry.creator);
+        }
+    }
+
+    pub fn list(&self) -> Vec<MatchInfo> {
+        self.matches.iter().map(|(id, m)| MatchInfo {
+            id: *id,
+            status: if m.playing { "playing" } else { "waiting" }.to_string(),
+            home: m.home,
+            away: m.away,
+            spectators: m.spectators,
+        }).collect()
+    }
+}
@@ -1,2 +1,4 @@
 pub mod handler;
-pub mod event;
\ No newline at end of file
+pub mod event;
+pub mod session;
+pub mod lobby;
\ No newline at end of file
@@ -0,0 +1,475 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::{Duration, Instant};
+
+use protocol::{frame, Action, ClientMessage, Command, FrameReader, PlayerAction, ServerMessage, Snapshot, BOTH_SIDES, SPECTATOR};
+
+use crate::game::game_state::GameState;
+use crate::game::models::Phase;
+use crate::network::event::ClientEvent;
+use crate::network::lobby::{MatchId, SharedLobby};
+
+// Bytes queued for a client before it counts as gone
+const MAX_PENDING: usize = 4 * frame::MAX_FRAME_SIZE;
+
+/// When a tick is played: as soon as every player answered, or on a fixed period.
+#[derive(Clone, Copy, PartialEq)]
+pub enum TickMode {
+    LockStep,
+    RealTime,
+}
+
+/// Server options shared by every match.
+#[derive(Clone, Copy)]
+pub struct Settings {
+    pub display_enable: bool,
+    pub run_time: bool,
+    pub tick_mode: TickMode,
+    pub tick_deadline: Duration,
+    pub seed: Option<u64>, // random for every match when not set
+}
+
+/// What a tick left of the match.
+enum Outcome {
+    Playing,
+    FullTime,
+    Lost, // a player can no longer be reached
+}
+
+/// Answer times of a client, logged when the match stops.
+#[derive(Default)]
+struct Latency {
+    answers: u32,
+    total: Duration,
+    max: Duration,
+    late: u32,   // answers to a tick already played, discarded
+    missed: u32, // ticks played without an answer, the server decided for its players
+}
+
+/// A client in a match, playing one team or both, or watching as a spectator.
+pub struct Seat {
+    pub side: char,
+    pub addr: SocketAddr,
+    version: u32,
+    stream: TcpStream,
+    reader: FrameReader,
+    // Bytes the socket did not take yet, written before anything new
+    pending: Vec<u8>,
+    // Decision of the current tick
+    message: Option<ClientMessage>,
+    // States sent and not answered yet, and when the last one left
+    owed: u32,
+    sent: Instant,
+    latency: Latency,
+}
+
+impl Seat {
+    pub fn new(side: char, addr: SocketAddr, version: u32, stream: TcpStream, reader: FrameReader) -> Seat {
+        Seat { side, addr, version, stream, reader, pending: vec![], message: None, owed: 0, sent: Instant::now(), latency: Latency::default() }
+    }
+
+    fn controls(&self, team: char) -> bool {
+        self.side == BOTH_SIDES || self.side == team
+    }
+
+    fn is_player(&self) -> bool {
+        self.side != SPECTATOR
+    }
+
+    /// Serialize and queue a message, then write what the socket takes, returns true when the stream is broken.
+    pub fn send(&mut self, message: &ServerMessage) -> bool {
+        if let ServerMessage::Welcome { .. } | ServerMessage::State(_) = message {
+            self.owed += 1;
+            self.sent = Instant::now();
+        }
+        self.pending.extend(frame::encode(&message.serialize()));
+        self.flush()
+    }
+
+    /// Write the queued bytes until the socket would block, returns true when the stream is broken
+    /// or the client stopped reading for too long.
+    fn flush(&mut self) -> bool {
+        while !self.pending.is_empty() {
+            match self.stream.write(&self.pending) {
+                Ok(0) => return true,
+                Ok(n) => { self.pending.drain(..n); },
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => return true,
+            }
+        }
+        self.pending.len() > MAX_PENDING
+    }
+
+    fn log_latency(&self) {
+        let latency = &self.latency;
+        let average = latency.total.as_millis() / latency.answers.max(1) as u128;
+        println!(
+            "Client {} latency: {} answers, average {} ms, max {} ms, {} late, {} missed",
+            self.addr, latency.answers, average, latency.max.as_millis(), latency.late, latency.missed
+        );
+    }
+
+    /// Complete messages received so far, an error once the client is gone.
+    fn receive(&mut self) -> Result<Vec<String>, String> {
+        let mut buffer = [0; 4096];
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => return Err("disconnected".to_string()),
+                Ok(n) => self.reader.push(&buffer[..n]),
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+        let mut inputs = vec![];
+        while let Some(input) = self.reader.next_frame().map_err(|e| e.to_string())? {
+            inputs.push(input);
+        }
+        Ok(inputs)
+    }
+}
+
+/// One match: a shared game played by one client for both teams, or by a home and an away client.
+pub struct Session {
+    id: MatchId,
+    game: GameState,
+    field: String,
+    seats: Vec<Seat>,
+    joiner: Receiver<Seat>,
+    started: bool,
+    // State sent on the last tick, the events of a tick are what changed since
+    last: Snapshot,
+}
+
+impl Session {
+    pub fn new(id: MatchId, game: GameState, field: String, seat: Seat, joiner: Receiver<Seat>) -> Session {
+        let last = game.snapshot();
+        Session { id, game, field, seats: vec![seat], joiner, started: false, last }
+    }
+
+    pub fn run(mut self, settings: Settings, tx: &Sender<ClientEvent>, lobby: &SharedLobby) {
+        if !self.is_complete() {
+            println!("Match {} waiting for the {} team", self.id, if self.seats[0].side == 'H' { 'A' } else { 'H' });
+        }
+        let mut tick_start = Instant::now();
+        loop {
+            // Clients seated by the lobby, spectators can come at any time
+            while let Ok(mut seat) = self.joiner.try_recv() {
+                println!("Client {} joins match {} as {}", seat.addr, self.id, seat.side);
+                if self.started && self.welcome(&mut seat) {
+                    lobby.lock().unwrap().leave(self.id);
+                    continue;
+                }
+                self.seats.push(seat);
+                // Home decisions are always merged first
+                self.seats.sort_by_key(|s| s.side != 'H');
+            }
+            if !self.started && self.is_complete() {
+                if self.start(settings, tx) {
+                    self.close("connection lost", settings, tx, lobby);
+                    return;
+                }
+                tick_start = Instant::now();
+            }
+
+            let mut index = 0;
+            while index < self.seats.len() {
+                let inputs = match self.seats[index].receive() {
+                    Ok(inputs) => inputs,
+                    Err(reason) => {
+                        println!("Client {} left match {}: {}", self.seats[index].addr, self.id, reason);
+                        if !self.seats[index].is_player() {
+                            self.seats.remove(index);
+                            lobby.lock().unwrap().leave(self.id);
+                            continue;
+                        }
+                        self.close(&reason, settings, tx, lobby);
+                        return;
+                    }
+                };
+                // What is left of earlier messages goes out first
+                let mut broken = self.seats[index].flush();
+                for input in inputs {
+                    if broken {
+                        break;
+                    }
+                    broken = self.handle_message(index, &input);
+                }
+                if broken {
+                    println!("Client {} of match {} is unreachable", self.seats[index].addr, self.id);
+                    // A lost spectator does not stop the match
+                    if !self.seats[index].is_player() {
+                        self.seats.remove(index);
+                        lobby.lock().unwrap().leave(self.id);
+                        continue;
+                    }
+                    self.close("connection lost", settings, tx, lobby);
+                    return;
+                }
+                index += 1;
+            }
+
+            // In lock-step every side answered, otherwise the deadline of the tick passed
+            let players = self.seats.iter().filter(|s| s.is_player()).count();
+            let answered = self.seats.iter().filter(|s| s.message.is_some()).count();
+            let ready = tick_start.elapsed() >= settings.tick_deadline
+                || (settings.tick_mode == TickMode::LockStep && answered == players);
+            if self.started && ready && let Some(message) = self.merge() {
+                match self.advance(message, settings, tx) {
+                    Outcome::Playing => {},
+                    Outcome::FullTime => {
+                        println!("Match {} over, closing the connections.", self.id);
+                        self.close("full time", settings, tx, lobby);
+                        return;
+                    },
+                    Outcome::Lost => {
+                        self.close("connection lost", settings, tx, lobby);
+                        return;
+                    },
+                }
+                tick_start = Instant::now();
+                continue;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    // Both teams have a client
+    fn is_complete(&self) -> bool {
+        let plays = |team: char| self.seats.iter().any(|s| s.is_player() && s.controls(team));
+        plays('H') && plays('A')
+    }
+
+    /// Kick off once both teams have a client, returns true when a player is unreachable.
+    fn start(&mut self, settings: Settings, tx: &Sender<ClientEvent>) -> bool {
+        self.started = true;
+        println!("Match {} kicks off", self.id);
+        if settings.display_enable {
+            std::thread::sleep(Duration::from_millis(240));
+            let _ = tx.send(ClientEvent::Initialized {
+                id: self.id,
+                field: self.field.clone(),
+                drawable: self.game.get_drawable(),
+            });
+        }
+        let mut seats = std::mem::take(&mut self.seats);
+        let broken = seats.iter_mut().any(|seat| self.welcome(seat) && seat.is_player());
+        self.seats = seats;
+        broken
+    }
+
+    /// Send the match and the current state to a new seat, returns true when the stream is broken.
+    fn welcome(&self, seat: &mut Seat) -> bool {
+        let message = ServerMessage::Welcome {
+            version: seat.version,
+            match_id: self.id,
+            side: seat.side,
+            snapshot: self.game.snapshot(),
+        };
+        if seat.send(&message) {
+            println!("Failed to send player positions to {}", seat.addr);
+            return true;
+        }
+        false
+    }
+
+    /// Handles one complete message from a seat, returns true when the connection must be closed.
+    fn handle_message(&mut self, index: usize, input: &str) -> bool {
+        let seat = &mut self.seats[index];
+        if seat.is_player() {
+            // The answer to a state already played comes too late
+            if seat.owed > 1 {
+                seat.owed -= 1;
+                seat.latency.late += 1;
+                return false;
+            }
+            seat.owed = 0;
+        }
+        let message = match ClientMessage::parse(input) {
+            Ok(message) => message,
+            Err(e) => {
+                println!("Invalid message from {}: {}", seat.addr, e);
+                return seat.send(&ServerMessage::Error { reason: e.to_string() });
+            }
+        };
+
+        match message {
+            // Spectators only watch
+            ClientMessage::Actions { .. } | ClientMessage::Command { .. } | ClientMessage::Substitute { .. } if !seat.is_player() => {
+                println!("Spectator {} tried to play in match {}", seat.addr, self.id);
+                seat.send(&ServerMessage::Error { reason: "spectators cannot play".to_string() })
+            }
+            // A client only replaces players of its own team
+            ClientMessage::Substitute { substitutions } => {
+                let substitutions = substitutions.into_iter().filter(|s| seat.controls(s.team)).collect();
+                self.game.dispatch(ClientMessage::Substitute { substitutions });
+                seat.send(&ServerMessage::State(self.game.snapshot()))
+            }
+            message @ (ClientMessage::Actions { .. } | ClientMessage::Command { .. }) => {
+                // One decision per tick, anything more waits for the next state
+                if seat.message.is_none() {
+                    let latency = seat.sent.elapsed();
+                    seat.latency.answers += 1;
+                    seat.latency.total += latency;
+                    seat.latency.max = seat.latency.max.max(latency);
+                    seat.message = Some(message);
+                }
+                false
+            }
+            ClientMessage::Init { .. } | ClientMessage::List | ClientMessage::Join { .. } => {
+                println!("Unrecognized input from {}: {}", seat.addr, input);
+                seat.send(&ServerMessage::Error { reason: "unexpected message".to_string() })
+            }
+        }
+    }
+
+    /// Merge the decisions of the tick, home before away so the order of arrival never matters.
+    /// Each client only moves its own players, a player left without a decision stays and the server decides set pieces for a silent client.
+    /// A set piece is decided by the team awarded it, a silent team takes the scrum when it is an option,
+    /// otherwise a conversion at the posts or a line out of its forwards.
+    fn merge(&self) -> Option<ClientMessage> {
+        let state = self.game.state.name.clone();
+
+        if state.starts_with("set-") {
+            let team = self.game.state.team;
+            for seat in &self.seats {
+                if let Some(ClientMessage::Command { command, .. }) = &seat.message
+                    && seat.controls(team) {
+                    return Some(ClientMessage::Command { state, command: command.clone() });
+                }
+            }
+            return match state.as_str() {
+                "set-penalty" | "set-free-kick" | "set-not-straight" => {
+                    print!("{}|{:.2}|{}|", self.game.addr, (self.game.time as f32)/100.0, state);
+                    println!("No decision from team {}, scrum", team);
+                    Some(ClientMessage::Command { state, command: Command::Scrum })
+                },
+                "set-transformation" => {
+                    let (number, distance) = (self.kicker(team), 10.0);
+                    let direction = self.aim_at_posts(team, distance);
+                    print!("{}|{:.2}|{}|", self.game.addr, (self.game.time as f32)/100.0, state);
+                    println!("No decision from team {}, conversion by player {} at the posts", team, number);
+                    Some(ClientMessage::Command { state, command: Command::Transformation { number, distance, direction, high: 30.0 } })
+                },
+                "set-line_out" => {
+                    let home_line = self.jumpers('H');
+                    let away_line = self.jumpers('A');
+                    print!("{}|{:.2}|{}|", self.game.addr, (self.game.time as f32)/100.0, state);
+                    println!("No decision from team {}, line out of {} players", team, home_line.len());
+                    Some(ClientMessage::Command { state, command: Command::LineOut { number: home_line.len(), home_line, away_line } })
+                },
+                // No option without parameters, the match waits for the team
+                _ => None,
+            };
+        }
+
+        let mut actions: Vec<PlayerAction> = vec![];
+        for team in ['H', 'A'] {
+            let decided = self.seats.iter().find_map(|seat| match &seat.message {
+                Some(ClientMessage::Actions { actions, .. }) if seat.controls(team) => Some(actions),
+                _ => None,
+            });
+            if let Some(decided) = decided {
+                actions.extend(decided.iter().filter(|a| a.team == team).cloned());
+            }
+            // A player without a decision stays, bound players keep pushing with the pack in a scrum or a maul
+            let players = if team == 'H' { &self.game.home_team.players } else { &self.game.away_team.players };
+            for player in players {
+                if !actions.iter().any(|a| a.team == team && a.number == player.number) {
+                    actions.push(PlayerAction { team, number: player.number, action: Action::Stay });
+                }
+            }
+        }
+        Some(ClientMessage::Actions { state, actions })
+    }
+
+    // Fly half of the team on the field, or its first player
+    fn kicker(&self, team: char) -> usize {
+        let players = if team == 'H' { &self.game.home_team.players } else { &self.game.away_team.players };
+        players.iter().find(|p| p.position == 10).or(players.first()).map_or(10, |p| p.number)
+    }
+
+    // Numbers of the forwards lining up in a line out, hooker apart as the thrower
+    fn jumpers(&self, team: char) -> Vec<usize> {
+        let players = if team == 'H' { &self.game.home_team.players } else { &self.game.away_team.players };
+        [1, 4, 6, 7, 5].iter().filter_map(|position| players.iter().find(|p| p.position == *position)).map(|p| p.number).collect()
+    }
+
+    // Direction from the conversion spot, `distance` in front of the try, straight to the posts
+    fn aim_at_posts(&self, team: char, distance: f32) -> f32 {
+        let post_y = self.game.field.height as f32 / 2.0 + 1.0;
+        (post_y - self.game.state.y).atan2(self.game.attack_sign(team) * distance).to_degrees()
+    }
+
+    /// Move the shared game one tick and send the new state to every seat.
+    fn advance(&mut self, message: ClientMessage, settings: Settings, tx: &Sender<ClientEvent>) -> Outcome {
+        let input = message.serialize();
+        let handled = self.game.dispatch(message);
+        for seat in self.seats.iter_mut().filter(|s| s.is_player()) {
+            if seat.message.take().is_none() {
+                seat.latency.missed += 1;
+            }
+        }
+        if !handled {
+            println!("Unrecognized input in match {}: {}", self.id, input);
+            let reason = format!("no handler for {}", input.lines().next().unwrap_or(""));
+            // Nothing moved, the clients decide again on the same state
+            if self.broadcast(&ServerMessage::Error { reason }) || self.broadcast(&ServerMessage::State(self.game.snapshot())) {
+                return Outcome::Lost;
+            }
+            return Outcome::Playing;
+        }
+
+        if settings.display_enable {
+            if settings.run_time {
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            let _ = tx.send(ClientEvent::DisplayUpdate {
+                id: self.id,
+                drawable: self.game.get_drawable(),
+            });
+        }
+
+        let mut snapshot = self.game.snapshot();
+        snapshot.events = snapshot.events_since(&self.last);
+        self.last = snapshot.clone();
+        if self.broadcast(&ServerMessage::State(snapshot)) {
+            Outcome::Lost
+        } else if self.game.phase == Phase::FullTime {
+            Outcome::FullTime
+        } else {
+            Outcome::Playing
+        }
+    }
+
+    // Send to every seat, returns true when a player is unreachable, a lost spectator does not stop the match
+    fn broadcast(&mut self, message: &ServerMessage) -> bool {
+        let mut broken = false;
+        for seat in self.seats.iter_mut() {
+            if seat.send(message) && seat.is_player() {
+                broken = true;
+            }
+        }
+        broken
+    }
+
+    // Tell the remaining clients why the match stops, then drop the connections
+    fn close(&mut self, reason: &str, settings: Settings, tx: &Sender<ClientEvent>, lobby: &SharedLobby) {
+        lobby.lock().unwrap().remove(self.id);
+        if self.game.phase != Phase::FullTime {
+            for seat in self.seats.iter_mut() {
+                let _ = seat.send(&ServerMessage::Error { reason: format!("match stopped: {}", reason) });
+            }
+        }
+        if settings.display_enable {
+            let _ = tx.send(ClientEvent::Finished(self.id));
+        }
+        for seat in &self.seats {
+            if seat.is_player() {
+                seat.log_latency();
+            }
+            println!("Client {} disconnected.", seat.addr);
+        }
+    }
+}